@@ -2,7 +2,7 @@ use criterion::{criterion_group, criterion_main, Criterion};
 use rand::prelude::IndexedRandom;
 use rand::rngs::SmallRng;
 use rand::SeedableRng;
-use spooky_connect4::bitboard::nw_for_board;
+use spooky_connect4::bitboard::{nw_for_board, BoardGeometry};
 use spooky_connect4::encode::encode_game_planes;
 use spooky_connect4::game::Game;
 use std::hint::black_box;
@@ -139,6 +139,25 @@ fn bench_encode_game_planes_19x19(c: &mut Criterion) {
     });
 }
 
+/// `column_height` with the geometry rebuilt per call versus held alongside
+/// the board, as the Python `Board` wrapper does.
+fn bench_column_height_geometry_19x19(c: &mut Criterion) {
+    let game = setup_midgame_19x19();
+    let board = *game.board();
+    let mut group = c.benchmark_group("column_height_19x19");
+    group.bench_function("rebuild_geometry", |b| {
+        b.iter(|| {
+            let geo = BoardGeometry::new(19, 19);
+            black_box(board.column_height(black_box(9), &geo))
+        })
+    });
+    let geo = BoardGeometry::new(19, 19);
+    group.bench_function("stored_geometry", |b| {
+        b.iter(|| black_box(board.column_height(black_box(9), &geo)))
+    });
+    group.finish();
+}
+
 fn bench_outcome(c: &mut Criterion) {
     let game = setup_midgame_9x9();
     c.bench_function("outcome", |b| b.iter(|| black_box(game.outcome())));
@@ -212,6 +231,7 @@ criterion_group!(
         bench_make_unmake,
         bench_encode_game_planes_9x9,
         bench_encode_game_planes_19x19,
+        bench_column_height_geometry_19x19,
         bench_outcome,
         bench_self_play_step,
 );
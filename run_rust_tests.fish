@@ -2,4 +2,11 @@ set SCRIPT_DIR (dirname (status --current-filename))
 cd $SCRIPT_DIR
 
 echo "Running Rust tests..."
-cargo test --no-default-features; or exit 1
+cargo test --no-default-features --features std; or exit 1
+cargo test --no-default-features --features std,serde; or exit 1
+cargo test --no-default-features --features std,log; or exit 1
+cargo test --no-default-features --features std,rand; or exit 1
+
+echo "Building without std..."
+cargo rustc --lib --no-default-features --crate-type rlib; or exit 1
+cargo rustc --lib --no-default-features --features serde --crate-type rlib; or exit 1
@@ -20,11 +20,16 @@ def augment_symmetries(
     npt.NDArray[np.float32],
     npt.NDArray[np.float32],
 ]: ...
+def step_many(games: list[Game], actions: list[int]) -> list[bool]: ...
+def legal_action_masks(games: list[Game]) -> list[list[bool]]: ...
+def nw_for_board(width: int, height: int) -> int: ...
 
 class Board:
     def __init__(self, width: int, height: int) -> None: ...
     @staticmethod
     def standard() -> Board: ...
+    @staticmethod
+    def from_grid(grid: list[list[int | None]]) -> Board: ...
     def width(self) -> int: ...
     def height(self) -> int: ...
     def get_piece(self, col: int, row: int) -> int | None: ...
@@ -33,6 +38,16 @@ class Board:
     def is_board_full(self) -> bool: ...
     def is_column_full(self, col: int) -> bool: ...
     def column_height(self, col: int) -> int: ...
+    def heights(self) -> list[int]: ...
+    def count(self, player: int) -> int: ...
+    def total_count(self) -> int: ...
+    def occupied_indices(self) -> list[tuple[int, int]]: ...
+    def pieces(self) -> list[tuple[int, int, int]]: ...
+    def __copy__(self) -> Board: ...
+    def __deepcopy__(self, memo: dict[int, object]) -> Board: ...
+    def __getnewargs__(self) -> tuple[int, int]: ...
+    def __getstate__(self) -> str: ...
+    def __setstate__(self, state: str) -> None: ...
     def __str__(self) -> str: ...
     def __repr__(self) -> str: ...
 
@@ -43,27 +58,46 @@ class Game:
     def width(self) -> int: ...
     def height(self) -> int: ...
     def get_piece(self, col: int, row: int) -> int | None: ...
+    def to_grid(self) -> list[list[int | None]]: ...
     def set_piece(self, col: int, row: int, piece: int | None) -> None: ...
     def turn(self) -> int: ...
     def is_over(self) -> bool: ...
+    def ply(self) -> int: ...
+    def move_number(self) -> int: ...
     def legal_action_indices(self) -> list[int]: ...
+    def legal_action_mask(self) -> list[bool]: ...
     def apply_action(self, action: int) -> bool: ...
-    def encode_game_planes(self) -> tuple[list[float], int, int, int]: ...
+    def apply_action_checked(self, action: int) -> str: ...
+    def encode_game_planes(self) -> npt.NDArray[np.float32]: ...
     def decode_action(self, action: int) -> Move | None: ...
     def total_actions(self) -> int: ...
     def board_shape(self) -> tuple[int, int]: ...
     def input_plane_count(self) -> int: ...
     def reward_absolute(self) -> float: ...
+    def terminal_value(self) -> float | None: ...
     def reward_from_perspective(self, perspective: int) -> float: ...
+    def winner(self) -> int | None: ...
+    def result_string(self) -> str: ...
     def outcome(self) -> GameOutcome | None: ...
+    def outcome_after(self, move_: Move) -> GameOutcome | None: ...
     def legal_moves(self) -> list[Move]: ...
     def is_legal_move(self, move_: Move) -> bool: ...
     def make_move(self, move_: Move) -> bool: ...
     def unmake_move(self) -> bool: ...
+    def last_move(self) -> Move | None: ...
+    def push(self, col: int) -> bool: ...
+    def pop(self) -> Move | None: ...
+    def reset(self) -> None: ...
     def board(self) -> Board: ...
     def clone(self) -> Game: ...
+    def __copy__(self) -> Game: ...
+    def __deepcopy__(self, memo: dict[int, object]) -> Game: ...
+    def __getnewargs__(self) -> tuple[int, int]: ...
+    def __getstate__(self) -> str: ...
+    def __setstate__(self, state: str) -> None: ...
     def state_hash(self) -> int: ...
     def transposition_hash(self) -> int: ...
+    def stable_hash(self) -> int: ...
     def __hash__(self) -> int: ...
     def __str__(self) -> str: ...
     def __repr__(self) -> str: ...
@@ -72,18 +106,31 @@ class Move:
     def __init__(self, col: int, row: int) -> None: ...
     def col(self) -> int: ...
     def row(self) -> int: ...
+    def is_valid_for(self, game: Game) -> bool: ...
     def encode(self) -> int: ...
     @staticmethod
     def decode(data: int, game: Game) -> Move: ...
     def __str__(self) -> str: ...
     def __repr__(self) -> str: ...
+    def __copy__(self) -> Move: ...
+    def __deepcopy__(self, memo: dict[int, object]) -> Move: ...
+    def __getnewargs__(self) -> tuple[int, int]: ...
     def __eq__(self, other: Move) -> bool: ...
+    def __lt__(self, other: Move) -> bool: ...
+    def __le__(self, other: Move) -> bool: ...
+    def __gt__(self, other: Move) -> bool: ...
+    def __ge__(self, other: Move) -> bool: ...
     def __hash__(self) -> int: ...
 
 class GameOutcome:
+    def __init__(self, name: str) -> None: ...
+    def __getnewargs__(self) -> tuple[str]: ...
     def winner(self) -> int | None: ...
+    def loser(self) -> int | None: ...
+    def is_decisive(self) -> bool: ...
     def encode_winner_absolute(self) -> float: ...
     def encode_winner_from_perspective(self, perspective: int) -> float: ...
+    def relative_to(self, player: int) -> str: ...
     def is_draw(self) -> bool: ...
     def __str__(self) -> str: ...
     def __repr__(self) -> str: ...
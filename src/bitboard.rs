@@ -1,12 +1,30 @@
-use std::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, Not};
+use alloc::vec::Vec;
+use core::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Not};
 
-use crate::limits::assert_valid_board_dimensions;
+use crate::limits::{assert_valid_board_dimensions, MAX_BOARD_DIMENSION};
+
+/// Number of stones in a row needed to win in standard Connect 4.
+pub const DEFAULT_CONNECT_LEN: u8 = 4;
+
+/// Range of connection lengths `BoardGeometry::with_connect_len` accepts.
+pub const MIN_CONNECT_LEN: u8 = 2;
+pub const MAX_CONNECT_LEN: u8 = MAX_BOARD_DIMENSION;
 
 /// Compute the number of u64 words needed for a board of given dimensions.
 pub const fn nw_for_board(width: u8, height: u8) -> usize {
     ((width as u16 * height as u16) as usize).div_ceil(64)
 }
 
+/// Index of the cell mirroring `index` left-to-right on a board `width`
+/// columns wide. Shared by `BoardGeometry::mirror_index_horizontal` and
+/// `Board::mirror_horizontal`, which has no geometry at hand.
+#[inline]
+pub(crate) fn mirror_index_horizontal(index: usize, width: usize) -> usize {
+    let row = index / width;
+    let col = index % width;
+    row * width + (width - 1 - col)
+}
+
 /// A fixed-size bitboard parameterized by the number of u64 words.
 /// `NW` = number of active words = ceil(width*height / 64).
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
@@ -30,12 +48,28 @@ impl<const NW: usize> Bitboard<NW> {
         bb
     }
 
+    /// Bits set at each of `indices`; duplicates are harmless. Each index
+    /// must be below `NW * 64` (checked in debug builds).
+    pub fn from_indices(indices: &[usize]) -> Self {
+        let mut bb = Self::empty();
+        for &index in indices {
+            bb.set(index);
+        }
+        bb
+    }
+
     /// Construct from raw words.
     #[inline]
     pub const fn from_words(words: [u64; NW]) -> Self {
         Bitboard { words }
     }
 
+    /// The raw words, bit 0 of word 0 being cell index 0.
+    #[inline]
+    pub fn words(&self) -> &[u64; NW] {
+        &self.words
+    }
+
     /// Test whether bit `index` is set.
     #[inline]
     pub fn get(&self, index: usize) -> bool {
@@ -57,6 +91,13 @@ impl<const NW: usize> Bitboard<NW> {
         self.words[index / 64] &= !(1u64 << (index % 64));
     }
 
+    /// Flip bit `index`.
+    #[inline]
+    pub fn toggle(&mut self, index: usize) {
+        debug_assert!(index < NW * 64);
+        self.words[index / 64] ^= 1u64 << (index % 64);
+    }
+
     /// True if no bits are set.
     #[inline]
     pub fn is_empty(&self) -> bool {
@@ -69,12 +110,41 @@ impl<const NW: usize> Bitboard<NW> {
         self.words.iter().any(|&w| w != 0)
     }
 
+    /// True if `self` and `other` have at least one set bit in common.
+    #[inline]
+    pub fn intersects(&self, other: &Self) -> bool {
+        self.words
+            .iter()
+            .zip(&other.words)
+            .any(|(&a, &b)| a & b != 0)
+    }
+
+    /// True if every bit set in `other` is also set in `self`.
+    #[inline]
+    pub fn contains(&self, other: &Self) -> bool {
+        self.words
+            .iter()
+            .zip(&other.words)
+            .all(|(&a, &b)| b & !a == 0)
+    }
+
     /// Population count — number of set bits.
     #[inline]
     pub fn count(&self) -> u32 {
         self.words.iter().map(|w| w.count_ones()).sum()
     }
 
+    /// Number of set bits within `mask`, i.e. `(self & mask).count()`, counted
+    /// word by word without building the intersection.
+    #[inline]
+    pub fn count_masked(&self, mask: &Bitboard<NW>) -> u32 {
+        self.words
+            .iter()
+            .zip(&mask.words)
+            .map(|(w, m)| (w & m).count_ones())
+            .sum()
+    }
+
     /// Index of the lowest set bit, or `None` if empty.
     #[inline]
     pub fn lowest_bit_index(&self) -> Option<usize> {
@@ -145,11 +215,15 @@ impl<const NW: usize> Bitboard<NW> {
     /// `self & !rhs` — bits in self that are not in rhs.
     #[inline]
     pub fn andnot(self, rhs: Bitboard<NW>) -> Bitboard<NW> {
-        let words = std::array::from_fn(|i| self.words[i] & !rhs.words[i]);
+        let words = core::array::from_fn(|i| self.words[i] & !rhs.words[i]);
         Bitboard { words }
     }
 
-    /// Iterate over indices of set bits.
+    /// Iterate over indices of set bits, in strictly ascending order.
+    ///
+    /// The order is guaranteed for any words, including ones built with
+    /// `from_words` that have bits past the board area, so callers may rely
+    /// on it for stable move generation.
     #[inline]
     pub fn iter_ones(&self) -> BitIterator<NW> {
         BitIterator {
@@ -157,13 +231,22 @@ impl<const NW: usize> Bitboard<NW> {
             word_index: 0,
         }
     }
+
+    /// Reverse the order of the low `area` bits, mapping bit `i` to
+    /// `area - 1 - i`. Bits at or above `area` must be clear.
+    pub fn reverse_bits_within(&self, area: u16) -> Self {
+        let area = area as usize;
+        debug_assert!(area <= NW * 64);
+        let words = core::array::from_fn(|i| self.words[NW - 1 - i].reverse_bits());
+        Bitboard { words }.shift_right(NW * 64 - area)
+    }
 }
 
 impl<const NW: usize> BitAnd for Bitboard<NW> {
     type Output = Bitboard<NW>;
     #[inline]
     fn bitand(self, rhs: Bitboard<NW>) -> Bitboard<NW> {
-        let words = std::array::from_fn(|i| self.words[i] & rhs.words[i]);
+        let words = core::array::from_fn(|i| self.words[i] & rhs.words[i]);
         Bitboard { words }
     }
 }
@@ -172,7 +255,7 @@ impl<const NW: usize> BitAnd for &Bitboard<NW> {
     type Output = Bitboard<NW>;
     #[inline]
     fn bitand(self, rhs: &Bitboard<NW>) -> Bitboard<NW> {
-        let words = std::array::from_fn(|i| self.words[i] & rhs.words[i]);
+        let words = core::array::from_fn(|i| self.words[i] & rhs.words[i]);
         Bitboard { words }
     }
 }
@@ -191,7 +274,7 @@ impl<const NW: usize> BitOr for Bitboard<NW> {
     type Output = Bitboard<NW>;
     #[inline]
     fn bitor(self, rhs: Bitboard<NW>) -> Bitboard<NW> {
-        let words = std::array::from_fn(|i| self.words[i] | rhs.words[i]);
+        let words = core::array::from_fn(|i| self.words[i] | rhs.words[i]);
         Bitboard { words }
     }
 }
@@ -206,16 +289,62 @@ impl<const NW: usize> BitOrAssign for Bitboard<NW> {
     }
 }
 
+impl<const NW: usize> BitXor for Bitboard<NW> {
+    type Output = Bitboard<NW>;
+    #[inline]
+    fn bitxor(self, rhs: Bitboard<NW>) -> Bitboard<NW> {
+        let words = core::array::from_fn(|i| self.words[i] ^ rhs.words[i]);
+        Bitboard { words }
+    }
+}
+
+impl<const NW: usize> BitXor for &Bitboard<NW> {
+    type Output = Bitboard<NW>;
+    #[inline]
+    fn bitxor(self, rhs: &Bitboard<NW>) -> Bitboard<NW> {
+        let words = core::array::from_fn(|i| self.words[i] ^ rhs.words[i]);
+        Bitboard { words }
+    }
+}
+
+impl<const NW: usize> BitXorAssign for Bitboard<NW> {
+    #[inline]
+    fn bitxor_assign(&mut self, rhs: Bitboard<NW>) {
+        self.words
+            .iter_mut()
+            .zip(rhs.words)
+            .for_each(|(w, r)| *w ^= r);
+    }
+}
+
 impl<const NW: usize> Not for Bitboard<NW> {
     type Output = Bitboard<NW>;
     #[inline]
     fn not(self) -> Bitboard<NW> {
-        let words = std::array::from_fn(|i| !self.words[i]);
+        let words = core::array::from_fn(|i| !self.words[i]);
         Bitboard { words }
     }
 }
 
-/// Iterator over set-bit indices in a `Bitboard`.
+#[cfg(feature = "serde")]
+impl<const NW: usize> serde::Serialize for Bitboard<NW> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.words.as_slice().serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, const NW: usize> serde::Deserialize<'de> for Bitboard<NW> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let words = Vec::<u64>::deserialize(deserializer)?;
+        let words: [u64; NW] = words.try_into().map_err(|words: Vec<u64>| {
+            serde::de::Error::invalid_length(words.len(), &alloc::format!("{} words", NW).as_str())
+        })?;
+        Ok(Bitboard { words })
+    }
+}
+
+/// Iterator over set-bit indices in a `Bitboard`, lowest index first.
 pub struct BitIterator<const NW: usize> {
     words: [u64; NW],
     word_index: u8,
@@ -240,12 +369,47 @@ impl<const NW: usize> Iterator for BitIterator<NW> {
     }
 }
 
+/// The direction of a completed line of stones.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum WinDirection {
+    Horizontal,
+    Vertical,
+    /// Rising from left to right.
+    DiagonalUp,
+    /// Falling from left to right.
+    DiagonalDown,
+}
+
+impl WinDirection {
+    /// All directions, in the order lines are searched.
+    pub const ALL: [WinDirection; 4] = [
+        WinDirection::Horizontal,
+        WinDirection::Vertical,
+        WinDirection::DiagonalUp,
+        WinDirection::DiagonalDown,
+    ];
+}
+
+impl core::fmt::Display for WinDirection {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            WinDirection::Horizontal => write!(f, "horizontal"),
+            WinDirection::Vertical => write!(f, "vertical"),
+            WinDirection::DiagonalUp => write!(f, "diagonal-up"),
+            WinDirection::DiagonalDown => write!(f, "diagonal-down"),
+        }
+    }
+}
+
 /// Precomputed masks for a given board geometry. Created once per Game.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct BoardGeometry<const NW: usize> {
     pub width: u8,
     pub height: u8,
     pub area: u16,
+    /// Number of stones in a row needed to win.
+    pub connect_len: u8,
     /// Mask with 1s at all valid board positions (indices 0..area).
     pub board_mask: Bitboard<NW>,
     /// board_mask minus column 0; applied after shift_left(1) to prevent wrap from col w-1 to col 0.
@@ -255,17 +419,52 @@ pub struct BoardGeometry<const NW: usize> {
 
     // Connect 4 specific masks
     /// One mask per column — bits set for all rows in that column. Only indices 0..width are valid.
-    pub column_masks: [Bitboard<NW>; 32],
+    pub column_masks: [Bitboard<NW>; MAX_BOARD_DIMENSION as usize],
     /// Bits set for the top row only (for board-full check).
     pub top_row_mask: Bitboard<NW>,
     /// Bits set for the bottom row only (row 0).
     pub bottom_row_mask: Bitboard<NW>,
+    /// Cells in columns 0..width-3, where a run of four heading right (or up
+    /// and right) starts without wrapping.
+    pub four_start_left: Bitboard<NW>,
+    /// Cells in columns 3..width, where a run of four heading up and left
+    /// starts without wrapping.
+    pub four_start_right: Bitboard<NW>,
+}
+
+/// One of the `BoardGeometry::shift_*` helpers: moves every bit one cell in
+/// a fixed direction, dropping bits that leave the board.
+type ShiftFn<const NW: usize> = fn(&BoardGeometry<NW>, &Bitboard<NW>) -> Bitboard<NW>;
+
+/// A line direction as used by the win and threat scans.
+#[derive(Clone, Copy)]
+struct LineDirection<const NW: usize> {
+    /// Index distance between consecutive cells along the line.
+    step: usize,
+    /// Shift one cell along the line, towards higher indices.
+    forward: ShiftFn<NW>,
+    /// Shift one cell back along the line.
+    backward: ShiftFn<NW>,
 }
 
 impl<const NW: usize> BoardGeometry<NW> {
-    /// Build geometry for a `width × height` board.
+    /// Build geometry for a `width × height` board with the standard
+    /// connection length of 4.
     pub fn new(width: u8, height: u8) -> Self {
+        Self::with_connect_len(width, height, DEFAULT_CONNECT_LEN)
+    }
+
+    /// Build geometry for a `width × height` board where `connect_len` stones
+    /// in a row win. `connect_len` must be between `MIN_CONNECT_LEN` and
+    /// `MAX_CONNECT_LEN`.
+    pub fn with_connect_len(width: u8, height: u8, connect_len: u8) -> Self {
         assert_valid_board_dimensions(width, height);
+        assert!(
+            (MIN_CONNECT_LEN..=MAX_CONNECT_LEN).contains(&connect_len),
+            "Connection length must be between {} and {}",
+            MIN_CONNECT_LEN,
+            MAX_CONNECT_LEN
+        );
         let area = width as u16 * height as u16;
         assert!(
             NW == (area as usize).div_ceil(64),
@@ -294,7 +493,7 @@ impl<const NW: usize> BoardGeometry<NW> {
         }
 
         // Column masks
-        let mut column_masks = [Bitboard::empty(); 32];
+        let mut column_masks = [Bitboard::empty(); MAX_BOARD_DIMENSION as usize];
         for (col, mask) in column_masks.iter_mut().enumerate().take(w) {
             *mask = Bitboard::empty();
             for row in 0..h {
@@ -314,30 +513,149 @@ impl<const NW: usize> BoardGeometry<NW> {
             bottom_row_mask.set(col);
         }
 
+        // Start cells for runs of four (see `four_starts_word`)
+        let mut four_start_left = Bitboard::empty();
+        let mut four_start_right = Bitboard::empty();
+        for row in 0..h {
+            for col in 0..w {
+                if col + 3 < w {
+                    four_start_left.set(row * w + col);
+                }
+                if col >= 3 {
+                    four_start_right.set(row * w + col);
+                }
+            }
+        }
+
         BoardGeometry {
             width,
             height,
             area,
+            connect_len,
             board_mask,
             not_col0,
             not_col_last,
             column_masks,
             top_row_mask,
             bottom_row_mask,
+            four_start_left,
+            four_start_right,
+        }
+    }
+
+    /// Cells in the middle column, or the two middle columns on even widths.
+    pub fn center_mask(&self) -> Bitboard<NW> {
+        let w = self.width as usize;
+        let mut mask = self.column_masks[w / 2];
+        if w.is_multiple_of(2) {
+            mask |= self.column_masks[w / 2 - 1];
         }
+        mask
+    }
+
+    /// Move every bit of `bb` one column right. Bits in the last column fall
+    /// off the board rather than wrapping to column 0 of the next row.
+    #[inline]
+    pub fn shift_right_col(&self, bb: &Bitboard<NW>) -> Bitboard<NW> {
+        bb.shift_left(1) & self.not_col0
+    }
+
+    /// Move every bit of `bb` one column left. Bits in column 0 fall off the
+    /// board rather than wrapping to the last column of the previous row.
+    #[inline]
+    pub fn shift_left_col(&self, bb: &Bitboard<NW>) -> Bitboard<NW> {
+        bb.shift_right(1) & self.not_col_last
+    }
+
+    /// Move every bit of `bb` one row up. Bits in the top row fall off.
+    #[inline]
+    pub fn shift_up_row(&self, bb: &Bitboard<NW>) -> Bitboard<NW> {
+        bb.shift_left(self.width as usize) & self.board_mask
+    }
+
+    /// Move every bit of `bb` one row down. Bits in the bottom row fall off.
+    #[inline]
+    pub fn shift_down_row(&self, bb: &Bitboard<NW>) -> Bitboard<NW> {
+        bb.shift_right(self.width as usize) & self.board_mask
+    }
+
+    /// Move every bit of `bb` one row up and one column right, dropping bits
+    /// that leave the board through the top row or the last column.
+    #[inline]
+    pub fn shift_up_right(&self, bb: &Bitboard<NW>) -> Bitboard<NW> {
+        bb.shift_left(self.width as usize + 1) & self.not_col0
+    }
+
+    /// Move every bit of `bb` one row up and one column left, dropping bits
+    /// that leave the board through the top row or column 0.
+    #[inline]
+    pub fn shift_up_left(&self, bb: &Bitboard<NW>) -> Bitboard<NW> {
+        bb.shift_left(self.width as usize - 1) & self.not_col_last
+    }
+
+    /// Move every bit of `bb` one row down and one column left, dropping bits
+    /// that leave the board through the bottom row or column 0.
+    #[inline]
+    pub fn shift_down_left(&self, bb: &Bitboard<NW>) -> Bitboard<NW> {
+        bb.shift_right(self.width as usize + 1) & self.not_col_last
+    }
+
+    /// Move every bit of `bb` one row down and one column right, dropping bits
+    /// that leave the board through the bottom row or the last column.
+    #[inline]
+    pub fn shift_down_right(&self, bb: &Bitboard<NW>) -> Bitboard<NW> {
+        bb.shift_right(self.width as usize - 1) & self.not_col0
     }
 
     /// Compute the set of all orthogonal neighbors of every bit in `bb`.
     #[inline]
     pub fn neighbors(&self, bb: &Bitboard<NW>) -> Bitboard<NW> {
-        let w = self.width as usize;
+        self.shift_right_col(bb)
+            | self.shift_left_col(bb)
+            | self.shift_up_row(bb)
+            | self.shift_down_row(bb)
+    }
+
+    /// Orthogonal neighbors of the single cell `index`, respecting the board
+    /// edges. Computed on demand rather than from a per-cell table, which
+    /// would bloat the geometry carried by every `Game`.
+    #[inline]
+    pub fn neighbors_of(&self, index: usize) -> Bitboard<NW> {
+        debug_assert!(index < self.area as usize);
+        self.neighbors(&Bitboard::single(index))
+    }
+
+    /// Rotate a bitboard by 180°, mapping cell `(col, row)` to
+    /// `(width - 1 - col, height - 1 - row)`.
+    ///
+    /// This is a board transform only: under gravity, a rotated position is
+    /// generally not reachable by legal play.
+    #[inline]
+    pub fn rotate_180(&self, bb: &Bitboard<NW>) -> Bitboard<NW> {
+        bb.reverse_bits_within(self.area)
+    }
 
-        let right = bb.shift_left(1) & self.not_col0;
-        let left = bb.shift_right(1) & self.not_col_last;
-        let up = bb.shift_left(w);
-        let down = bb.shift_right(w);
+    /// The cell index mirroring `index` left-to-right: column `c` becomes
+    /// `width - 1 - c` within the same row. Useful for remapping stored cell
+    /// indices (such as policy targets) when augmenting with mirrored boards.
+    #[inline]
+    pub fn mirror_index_horizontal(&self, index: usize) -> usize {
+        debug_assert!(index < self.area as usize);
+        mirror_index_horizontal(index, self.width as usize)
+    }
 
-        (right | left | up | down) & self.board_mask
+    /// Flip a bitboard top-to-bottom, sending row `r` to `height - 1 - r`.
+    /// Stones stop resting on the floor, so this is a transform utility for
+    /// data augmentation or analysis, not a symmetry of play.
+    pub fn flip_vertical(&self, bb: &Bitboard<NW>) -> Bitboard<NW> {
+        let w = self.width as usize;
+        let h = self.height as usize;
+        let mut flipped = Bitboard::empty();
+        for row in 0..h {
+            let row_bits = bb.shift_right(row * w) & self.bottom_row_mask;
+            flipped |= row_bits.shift_left((h - 1 - row) * w);
+        }
+        flipped
     }
 
     /// Flood-fill from `seed` through `mask`. Returns the connected component
@@ -355,79 +673,372 @@ impl<const NW: usize> BoardGeometry<NW> {
         }
     }
 
+    /// Like `flood_fill`, but gives up after `max_iters` expansion steps,
+    /// returning `None` if the fill has not reached a fixed point by then.
+    /// Each step grows the region by one orthogonal step, and the step that
+    /// adds nothing counts too.
+    pub fn flood_fill_bounded(
+        &self,
+        seed: Bitboard<NW>,
+        mask: Bitboard<NW>,
+        max_iters: usize,
+    ) -> Option<Bitboard<NW>> {
+        let mut filled = seed & mask;
+        for _ in 0..max_iters {
+            let nbrs = self.neighbors(&filled);
+            let expanded = (filled | nbrs) & mask;
+            if expanded == filled {
+                return Some(filled);
+            }
+            filled = expanded;
+        }
+        None
+    }
+
     /// Check if a player's bitboard has four in a row in any direction.
     ///
-    /// For each direction, we do 3 shift-and-AND steps, applying a column mask
-    /// at each step to prevent bits from wrapping across row boundaries.
+    /// Each direction takes a scalar fast path on boards that fit in a single
+    /// word (NW = 1, e.g. 7×6); the branch is on a constant and compiles away.
     #[inline]
     pub fn has_four_in_a_row(&self, bb: &Bitboard<NW>) -> bool {
+        self.has_horizontal(bb)
+            || self.has_vertical(bb)
+            || self.has_diagonal_up(bb)
+            || self.has_diagonal_down(bb)
+    }
+
+    /// Check if a player's bitboard has four in a row along a row.
+    #[inline]
+    pub fn has_horizontal(&self, bb: &Bitboard<NW>) -> bool {
+        if NW == 1 {
+            return Self::four_starts_word(bb.words[0], 1) & self.four_start_left.words[0] != 0;
+        }
+        self.run_ends_len(bb, Self::shift_right_col, 4).is_nonzero()
+    }
+
+    /// Check if a player's bitboard has four in a row up a column.
+    #[inline]
+    pub fn has_vertical(&self, bb: &Bitboard<NW>) -> bool {
         let w = self.width as usize;
+        if NW == 1 {
+            return Self::four_starts_word(bb.words[0], w) != 0;
+        }
+        self.run_ends_len(bb, Self::shift_up_row, 4).is_nonzero()
+    }
 
-        // Horizontal: shift_left(1) moves col c to col c+1.
-        // A bit at col w-1 would wrap to col 0 of the next row, so mask with not_col0.
-        {
-            let s1 = bb.shift_left(1) & self.not_col0;
-            let h2 = *bb & s1;
-            let s2 = s1.shift_left(1) & self.not_col0;
-            let h3 = h2 & s2;
-            let s3 = s2.shift_left(1) & self.not_col0;
-            let h4 = h3 & s3;
-            if h4.is_nonzero() {
-                return true;
+    /// Check if a player's bitboard has four in a row rising from left to
+    /// right.
+    #[inline]
+    pub fn has_diagonal_up(&self, bb: &Bitboard<NW>) -> bool {
+        let w = self.width as usize;
+        if NW == 1 {
+            return Self::four_starts_word(bb.words[0], w + 1) & self.four_start_left.words[0] != 0;
+        }
+        self.run_ends_len(bb, Self::shift_up_right, 4).is_nonzero()
+    }
+
+    /// Check if a player's bitboard has four in a row falling from left to
+    /// right.
+    #[inline]
+    pub fn has_diagonal_down(&self, bb: &Bitboard<NW>) -> bool {
+        let w = self.width as usize;
+        if NW == 1 {
+            return Self::four_starts_word(bb.words[0], w - 1) & self.four_start_right.words[0]
+                != 0;
+        }
+        self.run_ends_len(bb, Self::shift_up_left, 4).is_nonzero()
+    }
+
+    /// Bits of `x` that start a run of four set cells along `step`:
+    /// `x & x >> s & x >> 2s & x >> 3s`. Callers mask with the valid start
+    /// cells to rule out runs that wrap across rows. Bits past the board area
+    /// are zero, so vertical runs need no mask.
+    #[inline]
+    fn four_starts_word(x: u64, step: usize) -> u64 {
+        x & (x >> step) & (x >> (2 * step)) & (x >> (3 * step))
+    }
+
+    /// Check if a player's bitboard has `connect_len` in a row in any direction.
+    ///
+    /// Falls back to the unrolled `has_four_in_a_row` for the standard length.
+    #[inline]
+    pub fn has_n_in_a_row(&self, bb: &Bitboard<NW>) -> bool {
+        if self.connect_len == 4 {
+            return self.has_four_in_a_row(bb);
+        }
+        self.line_directions()
+            .iter()
+            .any(|dir| self.run_ends(bb, dir.forward).is_nonzero())
+    }
+
+    /// Every potential winning line on the board, each a bitboard with exactly
+    /// `connect_len` cells set, in `WinDirection::ALL` order and by starting
+    /// cell within each direction. A bitboard has `connect_len` in a row iff
+    /// it contains one of these lines.
+    pub fn winning_lines(&self) -> Vec<Bitboard<NW>> {
+        let (w, h) = (self.width as i32, self.height as i32);
+        let len = self.connect_len as i32;
+        let mut lines = Vec::new();
+
+        for (dc, dr) in [(1, 0), (0, 1), (1, 1), (-1, 1)] {
+            for row in 0..h {
+                for col in 0..w {
+                    let (end_col, end_row) = (col + dc * (len - 1), row + dr * (len - 1));
+                    if !(0..w).contains(&end_col) || end_row >= h {
+                        continue;
+                    }
+                    let mut line = Bitboard::empty();
+                    for k in 0..len {
+                        line.set(((row + dr * k) * w + col + dc * k) as usize);
+                    }
+                    lines.push(line);
+                }
             }
         }
 
-        // Vertical: shift_left(w) moves row r to row r+1.
-        // No column wrapping possible — pure row shift.
-        {
-            let s1 = bb.shift_left(w);
-            let v2 = *bb & s1;
-            let s2 = s1.shift_left(w);
-            let v3 = v2 & s2;
-            let s3 = s2.shift_left(w);
-            let v4 = v3 & s3;
-            if v4.is_nonzero() {
-                return true;
+        lines
+    }
+
+    /// Horizontal, vertical, ascending and descending lines, in
+    /// `WinDirection::ALL` order — the same directions `has_four_in_a_row`
+    /// checks.
+    #[inline]
+    fn line_directions(&self) -> [LineDirection<NW>; 4] {
+        let w = self.width as usize;
+        [
+            LineDirection {
+                step: 1,
+                forward: Self::shift_right_col,
+                backward: Self::shift_left_col,
+            },
+            LineDirection {
+                step: w,
+                forward: Self::shift_up_row,
+                backward: Self::shift_down_row,
+            },
+            LineDirection {
+                step: w + 1,
+                forward: Self::shift_up_right,
+                backward: Self::shift_down_left,
+            },
+            LineDirection {
+                step: w - 1,
+                forward: Self::shift_up_left,
+                backward: Self::shift_down_right,
+            },
+        ]
+    }
+
+    /// Bits of `bb` that end a run of `connect_len` set cells along the line
+    /// `forward` steps along. See `run_ends_len`.
+    #[inline]
+    fn run_ends(&self, bb: &Bitboard<NW>, forward: ShiftFn<NW>) -> Bitboard<NW> {
+        self.run_ends_len(bb, forward, self.connect_len)
+    }
+
+    /// Bits of `bb` that end a run of `len` set cells along the line `forward`
+    /// steps along, i.e. the bit and the `len - 1` cells before it are all
+    /// set. Done with `len - 1` shift-AND steps.
+    #[inline]
+    fn run_ends_len(&self, bb: &Bitboard<NW>, forward: ShiftFn<NW>, len: u8) -> Bitboard<NW> {
+        let mut ends = *bb;
+        let mut shifted = *bb;
+        for _ in 1..len {
+            shifted = forward(self, &shifted);
+            ends &= shifted;
+        }
+        ends
+    }
+
+    /// Check if a player's bitboard has four in a row on any line passing
+    /// through `index`. Returns false if `index` itself is not set.
+    ///
+    /// Only lines through the given cell are examined, so this is the cheap
+    /// check to run after dropping a stone at `index`: any newly completed four
+    /// must include it. Use `has_four_in_a_row` when the whole board may have
+    /// changed.
+    #[inline]
+    pub fn has_four_through(&self, bb: &Bitboard<NW>, index: usize) -> bool {
+        self.run_direction_through(bb, index, 4).is_some()
+    }
+
+    /// Like `has_four_through`, but for `connect_len` in a row.
+    #[inline]
+    pub fn has_n_through(&self, bb: &Bitboard<NW>, index: usize) -> bool {
+        self.win_direction_through(bb, index).is_some()
+    }
+
+    /// The direction of a line of `connect_len` set cells passing through
+    /// `index`, or `None` if there is none. If several lines pass through the
+    /// cell, the first in `WinDirection::ALL` order is returned.
+    #[inline]
+    pub fn win_direction_through(&self, bb: &Bitboard<NW>, index: usize) -> Option<WinDirection> {
+        self.run_direction_through(bb, index, self.connect_len as usize)
+    }
+
+    #[inline]
+    fn run_direction_through(
+        &self,
+        bb: &Bitboard<NW>,
+        index: usize,
+        len: usize,
+    ) -> Option<WinDirection> {
+        debug_assert!(index < self.area as usize);
+        if !bb.get(index) {
+            return None;
+        }
+        let w = self.width as isize;
+        let h = self.height as isize;
+        let col = index as isize % w;
+        let row = index as isize / w;
+
+        // (d_col, d_row) for each of `WinDirection::ALL`
+        const DIRECTIONS: [(isize, isize); 4] = [(1, 0), (0, 1), (1, 1), (1, -1)];
+
+        let run_length = |d_col: isize, d_row: isize| -> usize {
+            let mut length = 0;
+            let mut c = col + d_col;
+            let mut r = row + d_row;
+            while c >= 0 && c < w && r >= 0 && r < h && bb.get((r * w + c) as usize) {
+                length += 1;
+                c += d_col;
+                r += d_row;
+            }
+            length
+        };
+
+        DIRECTIONS
+            .iter()
+            .zip(WinDirection::ALL)
+            .find(|&(&(d_col, d_row), _)| {
+                1 + run_length(d_col, d_row) + run_length(-d_col, -d_row) >= len
+            })
+            .map(|(_, direction)| direction)
+    }
+
+    /// Find one completed line of `connect_len` stones in a player's bitboard,
+    /// returned as cell indices in ascending order.
+    ///
+    /// If several lines are complete, directions are tried in the order
+    /// horizontal, vertical, ascending diagonal, descending diagonal, and within
+    /// a direction the line whose last cell has the lowest index is returned.
+    pub fn winning_line(&self, bb: &Bitboard<NW>) -> Option<Vec<usize>> {
+        let len = self.connect_len as usize;
+
+        for dir in self.line_directions() {
+            if let Some(end) = self.run_ends(bb, dir.forward).lowest_bit_index() {
+                return Some((0..len).rev().map(|k| end - k * dir.step).collect());
             }
         }
 
-        // Diagonal / (ascending): shift_left(w+1) moves (row, col) to (row+1, col+1).
-        // The +1 col part can wrap, so mask with not_col0.
-        {
-            let s1 = bb.shift_left(w + 1) & self.not_col0;
-            let d2 = *bb & s1;
-            let s2 = s1.shift_left(w + 1) & self.not_col0;
-            let d3 = d2 & s2;
-            let s3 = s2.shift_left(w + 1) & self.not_col0;
-            let d4 = d3 & s3;
-            if d4.is_nonzero() {
-                return true;
+        None
+    }
+
+    /// The direction of the line `winning_line` would return.
+    pub fn winning_direction(&self, bb: &Bitboard<NW>) -> Option<WinDirection> {
+        self.line_directions()
+            .into_iter()
+            .zip(WinDirection::ALL)
+            .find(|(dir, _)| self.run_ends(bb, dir.forward).is_nonzero())
+            .map(|(_, direction)| direction)
+    }
+
+    /// Cells of `candidates` where a stone would complete a line of
+    /// `connect_len` with `player_bb`, in any direction.
+    ///
+    /// For each direction, a cell qualifies if it has `a` consecutive stones
+    /// behind it and `connect_len - 1 - a` ahead of it for some `a`. Both run
+    /// lengths are built with single-step shifts along the line.
+    pub fn completion_cells(
+        &self,
+        player_bb: &Bitboard<NW>,
+        candidates: &Bitboard<NW>,
+    ) -> Bitboard<NW> {
+        let n = self.connect_len as usize;
+        let stones = *player_bb & self.board_mask;
+
+        let mut cells = Bitboard::empty();
+        for dir in self.line_directions() {
+            // behind[k]: cells whose k predecessors along the line are stones
+            let mut behind = [self.board_mask; MAX_BOARD_DIMENSION as usize];
+            let mut shifted = stones;
+            for k in 1..n {
+                shifted = (dir.forward)(self, &shifted);
+                behind[k] = behind[k - 1] & shifted;
+            }
+
+            // ahead: cells whose k successors along the line are stones
+            let mut ahead = self.board_mask;
+            let mut shifted = stones;
+            for k in 0..n {
+                if k > 0 {
+                    shifted = (dir.backward)(self, &shifted);
+                    ahead &= shifted;
+                }
+                cells |= behind[n - 1 - k] & ahead;
             }
         }
 
-        // Diagonal \ (descending): shift_left(w-1) moves (row, col) to (row+1, col-1).
-        // The -1 col part can wrap (col 0 -> col w-1 of same row before the row shift),
-        // so mask with not_col_last.
-        {
-            let s1 = bb.shift_left(w - 1) & self.not_col_last;
-            let d2 = *bb & s1;
-            let s2 = s1.shift_left(w - 1) & self.not_col_last;
-            let d3 = d2 & s2;
-            let s3 = s2.shift_left(w - 1) & self.not_col_last;
-            let d4 = d3 & s3;
-            if d4.is_nonzero() {
-                return true;
+        cells & *candidates
+    }
+
+    /// Count three-in-a-row patterns of `player_bb` that an `empty` cell at
+    /// either end would extend to four, across all four directions.
+    ///
+    /// Each (three, completing cell) pair counts once, so a three that is open
+    /// at both ends counts twice and a three blocked at both ends not at all.
+    /// Pass the playable cells as `empty` to count only immediately
+    /// completable threats.
+    pub fn count_open_threes(&self, player_bb: &Bitboard<NW>, empty: &Bitboard<NW>) -> u32 {
+        let empty = *empty & self.board_mask;
+
+        let mut count = 0;
+        for dir in self.line_directions() {
+            // A bit survives in `threes` if it and the 2 cells before it along the line are set.
+            let s1 = (dir.forward)(self, player_bb);
+            let s2 = (dir.forward)(self, &s1);
+            let threes = *player_bb & s1 & s2;
+
+            // Cell after the last stone of the three
+            let after = (dir.forward)(self, &threes);
+
+            // Cell before the first stone of the three
+            let mut before = threes;
+            for _ in 0..3 {
+                before = (dir.backward)(self, &before);
             }
+
+            count += (after & empty).count() + (before & empty).count();
         }
 
-        false
+        count
     }
 }
 
 #[cfg(test)]
+#[allow(clippy::erasing_op, clippy::identity_op)]
 mod tests {
     use super::*;
 
+    /// A bitboard with each cell of `geo` set independently with probability
+    /// `p`, reproducible from `seed`.
+    fn random_bitboard<const NW: usize>(
+        geo: &BoardGeometry<NW>,
+        p: f64,
+        seed: u64,
+    ) -> Bitboard<NW> {
+        use rand::{RngExt, SeedableRng};
+
+        let mut rng = rand::rngs::SmallRng::seed_from_u64(seed);
+        let mut bb = Bitboard::empty();
+        for idx in 0..geo.area as usize {
+            if rng.random_bool(p) {
+                bb.set(idx);
+            }
+        }
+        bb
+    }
+
     #[test]
     fn test_empty() {
         let bb = Bitboard::<2>::empty();
@@ -485,6 +1096,65 @@ mod tests {
         assert!(or.get(20));
     }
 
+    #[test]
+    fn test_bitwise_xor() {
+        let a = Bitboard::<1>::single(5) | Bitboard::<1>::single(10);
+        let b = Bitboard::<1>::single(10) | Bitboard::<1>::single(20);
+
+        let xor = a ^ b;
+        assert!(xor.get(5));
+        assert!(!xor.get(10));
+        assert!(xor.get(20));
+
+        let mut assigned = a;
+        assigned ^= b;
+        assert_eq!(assigned, xor);
+        assigned ^= b;
+        assert_eq!(assigned, a);
+
+        // Cross word boundary
+        let c = Bitboard::<2>::single(63) | Bitboard::<2>::single(64);
+        let d = Bitboard::<2>::single(64) | Bitboard::<2>::single(65);
+        let indices: Vec<usize> = (c ^ d).iter_ones().collect();
+        assert_eq!(indices, vec![63, 65]);
+    }
+
+    #[test]
+    fn test_toggle() {
+        let mut bb = Bitboard::<2>::empty();
+        bb.toggle(70);
+        assert!(bb.get(70));
+        bb.toggle(3);
+        assert_eq!(bb.count(), 2);
+        bb.toggle(70);
+        assert!(!bb.get(70));
+        assert_eq!(bb, Bitboard::single(3));
+    }
+
+    #[test]
+    fn test_intersects_and_contains() {
+        let a = Bitboard::<2>::single(5) | Bitboard::<2>::single(70);
+        let disjoint = Bitboard::<2>::single(6) | Bitboard::<2>::single(71);
+        let overlapping = Bitboard::<2>::single(70) | Bitboard::<2>::single(100);
+        let subset = Bitboard::<2>::single(70);
+
+        assert!(!a.intersects(&disjoint));
+        assert!(!a.contains(&disjoint));
+
+        assert!(a.intersects(&overlapping));
+        assert!(overlapping.intersects(&a));
+        assert!(!a.contains(&overlapping));
+
+        assert!(a.intersects(&subset));
+        assert!(a.contains(&subset));
+        assert!(!subset.contains(&a));
+        assert!(a.contains(&a));
+
+        let empty = Bitboard::<2>::empty();
+        assert!(a.contains(&empty));
+        assert!(!a.intersects(&empty));
+    }
+
     #[test]
     fn test_shift_left() {
         let bb = Bitboard::<16>::single(0);
@@ -524,6 +1194,135 @@ mod tests {
         assert!(shifted3.is_empty());
     }
 
+    /// Check both shifts against a per-bit reference for every shift amount
+    /// within a word of `NW * 64`, on a board with bits around each word edge.
+    fn check_shift_boundaries<const NW: usize>() {
+        let total = NW * 64;
+        let mut indices = Vec::new();
+        for word in 0..NW {
+            indices.extend([word * 64, word * 64 + 1, word * 64 + 62, word * 64 + 63]);
+        }
+        let bb = Bitboard::<NW>::from_indices(&indices);
+
+        for n in (total - 65)..=(total + 1) {
+            let left = Bitboard::<NW>::from_indices(
+                &indices
+                    .iter()
+                    .filter_map(|&i| (i + n < total).then_some(i + n))
+                    .collect::<Vec<_>>(),
+            );
+            let right = Bitboard::<NW>::from_indices(
+                &indices
+                    .iter()
+                    .filter_map(|&i| i.checked_sub(n))
+                    .collect::<Vec<_>>(),
+            );
+            assert_eq!(bb.shift_left(n), left, "NW={} shift_left({})", NW, n);
+            assert_eq!(bb.shift_right(n), right, "NW={} shift_right({})", NW, n);
+        }
+
+        // The extreme bits survive a shift of `NW * 64 - 1` and no more.
+        let last = total - 1;
+        assert_eq!(
+            Bitboard::<NW>::single(0).shift_left(last),
+            Bitboard::single(last)
+        );
+        assert_eq!(
+            Bitboard::<NW>::single(last).shift_right(last),
+            Bitboard::single(0)
+        );
+        assert!(Bitboard::<NW>::single(0).shift_left(total).is_empty());
+        assert!(Bitboard::<NW>::single(last).shift_right(total).is_empty());
+    }
+
+    #[test]
+    fn test_shift_near_total_bits() {
+        check_shift_boundaries::<2>();
+        check_shift_boundaries::<6>();
+    }
+
+    #[test]
+    fn test_shift_rows_on_19x19() {
+        let geo = BoardGeometry::<{ nw_for_board(19, 19) }>::new(19, 19);
+        let w = 19;
+
+        // Rows 17 and 18 span the last two words; moving up keeps row 17,
+        // drops row 18, and moving back down restores the lower rows intact.
+        let second_top = geo.bottom_row_mask.shift_left(17 * w);
+        let top = geo.top_row_mask;
+        assert_eq!(geo.shift_up_row(&second_top), top);
+        assert!(geo.shift_up_row(&top).is_empty());
+        assert_eq!(geo.shift_down_row(&top), second_top);
+
+        let up = geo.shift_up_row(&geo.board_mask);
+        assert_eq!(up, geo.board_mask.andnot(geo.bottom_row_mask));
+        assert_eq!(
+            geo.shift_down_row(&up),
+            geo.board_mask.andnot(geo.top_row_mask)
+        );
+    }
+
+    #[test]
+    fn test_reverse_bits_within() {
+        let bb = Bitboard::<2>::single(0) | Bitboard::<2>::single(5) | Bitboard::<2>::single(70);
+        let reversed = bb.reverse_bits_within(81);
+        let indices: Vec<usize> = reversed.iter_ones().collect();
+        assert_eq!(indices, vec![10, 75, 80]);
+        assert_eq!(reversed.reverse_bits_within(81), bb);
+
+        // Full width: no shift
+        let bb = Bitboard::<1>::single(0);
+        assert_eq!(bb.reverse_bits_within(64), Bitboard::single(63));
+    }
+
+    #[test]
+    fn test_flip_vertical() {
+        let geo = BoardGeometry::<{ nw_for_board(9, 8) }>::new(9, 8);
+        let (w, h) = (9usize, 8usize);
+
+        let bottom = Bitboard::single(4);
+        assert_eq!(
+            geo.flip_vertical(&bottom),
+            Bitboard::single((h - 1) * w + 4)
+        );
+        assert_eq!(geo.flip_vertical(&geo.bottom_row_mask), geo.top_row_mask);
+        assert_eq!(geo.flip_vertical(&geo.board_mask), geo.board_mask);
+
+        let mut bb = Bitboard::empty();
+        for idx in [0, 3, 13, 40, 63, 64, 71] {
+            bb.set(idx);
+        }
+        let flipped = geo.flip_vertical(&bb);
+        let mut expected = Bitboard::empty();
+        for idx in bb.iter_ones() {
+            let (col, row) = (idx % w, idx / w);
+            expected.set((h - 1 - row) * w + col);
+        }
+        assert_eq!(flipped, expected);
+        assert_eq!(geo.flip_vertical(&flipped), bb);
+    }
+
+    #[test]
+    fn test_rotate_180() {
+        let geo = BoardGeometry::<{ nw_for_board(9, 9) }>::new(9, 9);
+        let (w, h) = (9usize, 9usize);
+
+        let mut bb = Bitboard::empty();
+        for idx in [0, 3, 13, 40, 63, 64, 79] {
+            bb.set(idx);
+        }
+        let rotated = geo.rotate_180(&bb);
+
+        let mut expected = Bitboard::empty();
+        for idx in bb.iter_ones() {
+            let (col, row) = (idx % w, idx / w);
+            expected.set((h - 1 - row) * w + (w - 1 - col));
+        }
+        assert_eq!(rotated, expected);
+        assert_eq!(geo.rotate_180(&rotated), bb);
+        assert!((rotated & !geo.board_mask).is_empty());
+    }
+
     #[test]
     fn test_shift_by_width() {
         // Simulate shift by width=9 (row shift on 9x9 board)
@@ -540,6 +1339,45 @@ mod tests {
         assert_eq!(indices, vec![3, 64, 200]);
     }
 
+    #[test]
+    fn test_from_indices() {
+        let indices = [200, 3, 64, 127, 3];
+        let bb = Bitboard::<4>::from_indices(&indices);
+        let expected = indices
+            .iter()
+            .fold(Bitboard::<4>::empty(), |acc, &i| acc | Bitboard::single(i));
+        assert_eq!(bb, expected);
+        assert_eq!(bb.iter_ones().collect::<Vec<_>>(), vec![3, 64, 127, 200]);
+        assert!(Bitboard::<4>::from_indices(&[]).is_empty());
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic]
+    fn test_from_indices_out_of_range() {
+        Bitboard::<1>::from_indices(&[64]);
+    }
+
+    #[test]
+    fn test_iter_ones_ascending_on_random_words() {
+        use rand::{RngExt, SeedableRng};
+
+        let mut rng = rand::rngs::SmallRng::seed_from_u64(0xb175);
+        for _ in 0..2_000 {
+            let words: [u64; 3] = core::array::from_fn(|_| match rng.random_range(0..4) {
+                0 => 0,
+                1 => u64::MAX,
+                _ => rng.random(),
+            });
+            let bb = Bitboard::<3>::from_words(words);
+            let indices: Vec<usize> = bb.iter_ones().collect();
+
+            assert!(indices.windows(2).all(|pair| pair[0] < pair[1]));
+            let expected: Vec<usize> = (0..3 * 64).filter(|&i| bb.get(i)).collect();
+            assert_eq!(indices, expected);
+        }
+    }
+
     #[test]
     fn test_iter_ones_empty() {
         let bb = Bitboard::<2>::empty();
@@ -590,6 +1428,40 @@ mod tests {
         assert_eq!(nw_for_board(32, 32), 16); // 1024 bits
     }
 
+    #[test]
+    fn test_geometry_boundary_sizes() {
+        let geo = BoardGeometry::<{ nw_for_board(32, 32) }>::new(32, 32);
+        assert_eq!(geo.area, 1024);
+        assert_eq!(geo.board_mask.count(), 1024);
+        assert_eq!(geo.column_masks[31].count(), 32);
+        assert_eq!(geo.top_row_mask.lowest_bit_index(), Some(31 * 32));
+
+        let geo = BoardGeometry::<{ nw_for_board(4, 4) }>::new(4, 4);
+        assert_eq!(geo.board_mask.count(), 16);
+
+        let geo = BoardGeometry::<{ nw_for_board(4, 32) }>::new(4, 32);
+        assert_eq!(geo.board_mask.count(), 128);
+        assert_eq!(geo.column_masks[3].count(), 32);
+    }
+
+    #[test]
+    #[should_panic(expected = "Board width must be between 4 and 32")]
+    fn test_geometry_too_wide() {
+        BoardGeometry::<{ nw_for_board(33, 32) }>::new(33, 32);
+    }
+
+    #[test]
+    #[should_panic(expected = "Board height must be between 4 and 32")]
+    fn test_geometry_too_short() {
+        BoardGeometry::<{ nw_for_board(4, 3) }>::new(4, 3);
+    }
+
+    #[test]
+    #[should_panic(expected = "does not match board")]
+    fn test_geometry_wrong_nw() {
+        BoardGeometry::<1>::new(9, 9);
+    }
+
     #[test]
     fn test_geometry_7x6() {
         let geo = BoardGeometry::<{ nw_for_board(7, 6) }>::new(7, 6);
@@ -696,4 +1568,565 @@ mod tests {
         bb.set(3 * w + 1);
         assert!(!geo.has_four_in_a_row(&bb));
     }
+
+    #[test]
+    fn test_has_four_through_directions() {
+        let geo = BoardGeometry::<{ nw_for_board(7, 6) }>::new(7, 6);
+        let w = 7usize;
+
+        // Horizontal: row 2, cols 1..=4
+        let mut bb = Bitboard::empty();
+        for col in 1..5 {
+            bb.set(2 * w + col);
+        }
+        for col in 1..5 {
+            assert!(geo.has_four_through(&bb, 2 * w + col));
+        }
+        assert!(!geo.has_four_through(&bb, 2 * w));
+        assert!(!geo.has_four_through(&bb, 3 * w + 2));
+
+        // Descending diagonal: (0,6), (1,5), (2,4), (3,3)
+        let mut bb = Bitboard::empty();
+        for i in 0..4 {
+            bb.set(i * w + (6 - i));
+        }
+        assert!(geo.has_four_through(&bb, 0 * w + 6));
+        assert!(geo.has_four_through(&bb, 3 * w + 3));
+    }
+
+    #[test]
+    fn test_win_direction_through() {
+        let geo = BoardGeometry::<{ nw_for_board(7, 6) }>::new(7, 6);
+        let w = 7usize;
+
+        let line = |cells: [(usize, usize); 4]| {
+            let mut bb = Bitboard::empty();
+            for (col, row) in cells {
+                bb.set(row * w + col);
+            }
+            bb
+        };
+        let cases = [
+            (
+                line([(1, 2), (2, 2), (3, 2), (4, 2)]),
+                WinDirection::Horizontal,
+            ),
+            (
+                line([(5, 1), (5, 2), (5, 3), (5, 4)]),
+                WinDirection::Vertical,
+            ),
+            (
+                line([(0, 0), (1, 1), (2, 2), (3, 3)]),
+                WinDirection::DiagonalUp,
+            ),
+            (
+                line([(6, 0), (5, 1), (4, 2), (3, 3)]),
+                WinDirection::DiagonalDown,
+            ),
+        ];
+        for (bb, direction) in cases {
+            for idx in bb.iter_ones() {
+                assert_eq!(geo.win_direction_through(&bb, idx), Some(direction));
+            }
+            assert_eq!(geo.winning_direction(&bb), Some(direction));
+        }
+
+        // Horizontal and vertical lines sharing a cell: horizontal comes first
+        let both = line([(0, 0), (1, 0), (2, 0), (3, 0)]) | line([(3, 0), (3, 1), (3, 2), (3, 3)]);
+        assert_eq!(
+            geo.win_direction_through(&both, 3),
+            Some(WinDirection::Horizontal)
+        );
+        assert_eq!(
+            geo.win_direction_through(&both, 3 * w + 3),
+            Some(WinDirection::Vertical)
+        );
+        assert_eq!(geo.winning_direction(&both), Some(WinDirection::Horizontal));
+        assert_eq!(geo.winning_direction(&Bitboard::empty()), None);
+    }
+
+    #[test]
+    fn test_has_four_through_no_wraparound() {
+        let geo = BoardGeometry::<{ nw_for_board(7, 6) }>::new(7, 6);
+        let w = 7usize;
+
+        // cols 5,6 of row 0 + cols 0,1 of row 1 are adjacent indices but not a line
+        let mut bb = Bitboard::empty();
+        bb.set(0 * w + 5);
+        bb.set(0 * w + 6);
+        bb.set(1 * w + 0);
+        bb.set(1 * w + 1);
+        assert!(!geo.has_four_through(&bb, 0 * w + 6));
+        assert!(!geo.has_four_through(&bb, 1 * w + 0));
+    }
+
+    #[test]
+    fn test_has_four_through_agrees_with_full_scan() {
+        fn check<const NW: usize>(width: u8, height: u8) {
+            let geo = BoardGeometry::<NW>::new(width, height);
+
+            for seed in 0..2_000 {
+                let bb = random_bitboard(&geo, 0.4, seed);
+                let through_any = bb.iter_ones().any(|idx| geo.has_four_through(&bb, idx));
+                assert_eq!(through_any, geo.has_four_in_a_row(&bb));
+            }
+        }
+
+        check::<{ nw_for_board(7, 6) }>(7, 6);
+        check::<{ nw_for_board(9, 9) }>(9, 9);
+        check::<{ nw_for_board(4, 4) }>(4, 4);
+        check::<{ nw_for_board(19, 19) }>(19, 19);
+    }
+
+    #[test]
+    fn test_has_four_single_word_matches_generic() {
+        for (width, height) in [(7, 6), (4, 4), (8, 8), (16, 4), (4, 16), (5, 7)] {
+            let geo = BoardGeometry::<1>::new(width, height);
+            let mut wins = 0;
+            for seed in 0..5_000 {
+                // Densities from 0.1 to 0.7, sparse boards to crowded ones.
+                let density = 0.1 + 0.1 * (seed % 7) as f64;
+                let bb = random_bitboard(&geo, density, seed);
+
+                let generic = geo
+                    .line_directions()
+                    .map(|dir| geo.run_ends_len(&bb, dir.forward, 4).is_nonzero());
+                let fast = [
+                    geo.has_horizontal(&bb),
+                    geo.has_vertical(&bb),
+                    geo.has_diagonal_up(&bb),
+                    geo.has_diagonal_down(&bb),
+                ];
+                assert_eq!(
+                    fast, generic,
+                    "{}x{} board {:#x}",
+                    width, height, bb.words[0]
+                );
+                let expected = generic.contains(&true);
+                assert_eq!(geo.has_four_in_a_row(&bb), expected);
+                wins += expected as u32;
+            }
+            assert!(wins > 0 && wins < 5_000);
+        }
+    }
+
+    #[test]
+    fn test_directional_win_checks() {
+        fn check<const NW: usize>(width: u8, height: u8) {
+            let geo = BoardGeometry::<NW>::new(width, height);
+            let w = width as usize;
+            let line = |cells: [(usize, usize); 4]| {
+                let mut bb = Bitboard::<NW>::empty();
+                for (col, row) in cells {
+                    bb.set(row * w + col);
+                }
+                bb
+            };
+            let lines = [
+                line([(1, 2), (2, 2), (3, 2), (4, 2)]),
+                line([(3, 0), (3, 1), (3, 2), (3, 3)]),
+                line([(1, 1), (2, 2), (3, 3), (4, 4)]),
+                line([(4, 1), (3, 2), (2, 3), (1, 4)]),
+            ];
+
+            for (i, bb) in lines.iter().enumerate() {
+                let found = [
+                    geo.has_horizontal(bb),
+                    geo.has_vertical(bb),
+                    geo.has_diagonal_up(bb),
+                    geo.has_diagonal_down(bb),
+                ];
+                let mut expected = [false; 4];
+                expected[i] = true;
+                assert_eq!(found, expected, "{}x{} line {}", width, height, i);
+                assert!(geo.has_four_in_a_row(bb));
+            }
+
+            // Runs that would only connect by wrapping across a row edge.
+            let wrapped = line([(w - 2, 0), (w - 1, 0), (0, 1), (1, 1)]);
+            assert!(!geo.has_horizontal(&wrapped));
+            let wrapped_diag = line([(w - 2, 0), (w - 1, 1), (0, 3), (1, 4)]);
+            assert!(!geo.has_diagonal_up(&wrapped_diag));
+        }
+
+        check::<{ nw_for_board(7, 6) }>(7, 6);
+        check::<{ nw_for_board(9, 9) }>(9, 9);
+    }
+
+    #[test]
+    fn test_directional_shifts() {
+        fn check<const NW: usize>(width: u8, height: u8) {
+            let geo = BoardGeometry::<NW>::new(width, height);
+            let (w, h) = (width as usize, height as usize);
+            let at = |col: usize, row: usize| Bitboard::<NW>::single(row * w + col);
+
+            for row in 0..h {
+                for col in 0..w {
+                    let bb = at(col, row);
+                    let expect = |ok: bool, c: usize, r: usize| {
+                        if ok {
+                            at(c, r)
+                        } else {
+                            Bitboard::empty()
+                        }
+                    };
+                    assert_eq!(geo.shift_right_col(&bb), expect(col + 1 < w, col + 1, row));
+                    assert_eq!(
+                        geo.shift_left_col(&bb),
+                        expect(col > 0, col.wrapping_sub(1), row)
+                    );
+                    assert_eq!(geo.shift_up_row(&bb), expect(row + 1 < h, col, row + 1));
+                    assert_eq!(
+                        geo.shift_down_row(&bb),
+                        expect(row > 0, col, row.wrapping_sub(1))
+                    );
+                    assert_eq!(
+                        geo.shift_up_right(&bb),
+                        expect(row + 1 < h && col + 1 < w, col + 1, row + 1)
+                    );
+                    assert_eq!(
+                        geo.shift_up_left(&bb),
+                        expect(row + 1 < h && col > 0, col.wrapping_sub(1), row + 1)
+                    );
+                    assert_eq!(
+                        geo.shift_down_left(&bb),
+                        expect(row > 0 && col > 0, col.wrapping_sub(1), row.wrapping_sub(1))
+                    );
+                    assert_eq!(
+                        geo.shift_down_right(&bb),
+                        expect(row > 0 && col + 1 < w, col + 1, row.wrapping_sub(1))
+                    );
+                }
+            }
+        }
+
+        check::<{ nw_for_board(7, 6) }>(7, 6);
+        check::<{ nw_for_board(9, 9) }>(9, 9);
+        check::<{ nw_for_board(8, 8) }>(8, 8);
+    }
+
+    #[test]
+    fn test_mirror_index_horizontal() {
+        let geo = BoardGeometry::<{ nw_for_board(7, 6) }>::new(7, 6);
+        for row in 0..6 {
+            for col in 0..7 {
+                let index = row * 7 + col;
+                let mirrored = geo.mirror_index_horizontal(index);
+                assert_eq!(mirrored, row * 7 + (6 - col));
+                assert_eq!(geo.mirror_index_horizontal(mirrored), index);
+            }
+        }
+    }
+
+    #[test]
+    fn test_count_masked() {
+        let bb = Bitboard::<3>::from_indices(&[0, 5, 63, 64, 100, 150, 191]);
+        let mask = Bitboard::<3>::from_indices(&[5, 64, 65, 150, 190]);
+        assert_eq!(bb.count_masked(&mask), (bb & mask).count());
+        assert_eq!(bb.count_masked(&mask), 3);
+        assert_eq!(bb.count_masked(&Bitboard::empty()), 0);
+        assert_eq!(bb.count_masked(&!Bitboard::empty()), bb.count());
+    }
+
+    #[test]
+    fn test_center_mask_counts() {
+        fn check<const NW: usize>(width: u8, height: u8, center_cols: &[usize]) {
+            let geo = BoardGeometry::<NW>::new(width, height);
+            let w = width as usize;
+            let center = geo.center_mask();
+            assert_eq!(center.count() as usize, center_cols.len() * height as usize);
+
+            // Stones on every third cell, tallied by hand against the columns.
+            let stones = Bitboard::<NW>::from_indices(
+                &(0..geo.area as usize).step_by(3).collect::<Vec<_>>(),
+            );
+            let manual = stones
+                .iter_ones()
+                .filter(|idx| center_cols.contains(&(idx % w)))
+                .count() as u32;
+            assert_eq!(stones.count_masked(&center), manual);
+        }
+
+        check::<{ nw_for_board(7, 6) }>(7, 6, &[3]);
+        check::<{ nw_for_board(8, 8) }>(8, 8, &[3, 4]);
+        check::<{ nw_for_board(9, 9) }>(9, 9, &[4]);
+        check::<{ nw_for_board(4, 4) }>(4, 4, &[1, 2]);
+    }
+
+    #[test]
+    fn test_neighbors_of() {
+        let geo = BoardGeometry::<{ nw_for_board(7, 6) }>::new(7, 6);
+        let cells = |list: &[usize]| Bitboard::from_indices(list);
+
+        // Corners, an edge cell and an interior cell.
+        assert_eq!(geo.neighbors_of(0), cells(&[1, 7]));
+        assert_eq!(geo.neighbors_of(6), cells(&[5, 13]));
+        assert_eq!(geo.neighbors_of(35), cells(&[28, 36]));
+        assert_eq!(geo.neighbors_of(41), cells(&[34, 40]));
+        assert_eq!(geo.neighbors_of(14), cells(&[7, 15, 21]));
+        assert_eq!(geo.neighbors_of(17), cells(&[10, 16, 18, 24]));
+
+        // Across the word boundary on a two-word board.
+        let wide = BoardGeometry::<{ nw_for_board(9, 9) }>::new(9, 9);
+        assert_eq!(wide.neighbors_of(63), Bitboard::from_indices(&[54, 64, 72]));
+
+        for index in 0..geo.area as usize {
+            assert_eq!(
+                geo.neighbors_of(index),
+                geo.neighbors(&Bitboard::single(index))
+            );
+        }
+    }
+
+    #[test]
+    fn test_flood_fill_bounded() {
+        let geo = BoardGeometry::<{ nw_for_board(7, 6) }>::new(7, 6);
+        let seed = Bitboard::single(0);
+
+        // From a corner the far corner is 6 + 5 steps away, plus one step
+        // to see that nothing more is added: well under the 42 cells.
+        let full = geo.flood_fill(seed, geo.board_mask);
+        assert_eq!(full, geo.board_mask);
+        assert_eq!(geo.flood_fill_bounded(seed, geo.board_mask, 12), Some(full));
+        assert_eq!(geo.flood_fill_bounded(seed, geo.board_mask, 11), None);
+
+        // A wall in column 3 confines the fill to the left three columns.
+        let wall = geo.column_masks[3];
+        let mask = geo.board_mask.andnot(wall);
+        let left = geo.flood_fill_bounded(seed, mask, geo.area as usize);
+        assert_eq!(left, Some(geo.flood_fill(seed, mask)));
+        assert_eq!(left.map(|bb| bb.count()), Some(18));
+
+        assert_eq!(
+            geo.flood_fill_bounded(seed, Bitboard::empty(), 1),
+            Some(Bitboard::empty())
+        );
+        assert_eq!(geo.flood_fill_bounded(seed, geo.board_mask, 0), None);
+    }
+
+    #[test]
+    fn test_winning_lines() {
+        let geo = BoardGeometry::<{ nw_for_board(7, 6) }>::new(7, 6);
+        let lines = geo.winning_lines();
+        // 24 horizontal + 21 vertical + 12 per diagonal direction.
+        assert_eq!(lines.len(), 69);
+        assert!(lines.iter().all(|line| line.count() == 4));
+        assert!(lines.iter().all(|line| geo.board_mask.contains(line)));
+
+        for seed in 0..2_000 {
+            let bb = random_bitboard(&geo, 0.35, seed);
+            let covered = lines.iter().any(|line| bb.contains(line));
+            assert_eq!(covered, geo.has_four_in_a_row(&bb));
+        }
+
+        let wide = BoardGeometry::<{ nw_for_board(9, 9) }>::with_connect_len(9, 9, 5);
+        // (9 - 4) * 9 per straight direction, (9 - 4)^2 per diagonal.
+        assert_eq!(wide.winning_lines().len(), 2 * 45 + 2 * 25);
+        assert!(wide.winning_lines().iter().all(|line| line.count() == 5));
+    }
+
+    #[test]
+    fn test_winning_line() {
+        let geo = BoardGeometry::<{ nw_for_board(7, 6) }>::new(7, 6);
+        let w = 7usize;
+
+        assert_eq!(geo.winning_line(&Bitboard::empty()), None);
+
+        // Vertical: col 2, rows 1..=4
+        let mut bb = Bitboard::empty();
+        for row in 1..5 {
+            bb.set(row * w + 2);
+        }
+        assert_eq!(
+            geo.winning_line(&bb),
+            Some(vec![1 * w + 2, 2 * w + 2, 3 * w + 2, 4 * w + 2])
+        );
+
+        // Descending diagonal: (0,3), (1,2), (2,1), (3,0)
+        let mut bb = Bitboard::empty();
+        for i in 0..4 {
+            bb.set(i * w + (3 - i));
+        }
+        assert_eq!(
+            geo.winning_line(&bb),
+            Some(vec![0 * w + 3, 1 * w + 2, 2 * w + 1, 3 * w + 0])
+        );
+
+        // Wrapping pattern is not a line
+        let mut bb = Bitboard::empty();
+        bb.set(0 * w + 5);
+        bb.set(1 * w + 6);
+        bb.set(2 * w + 0);
+        bb.set(3 * w + 1);
+        assert_eq!(geo.winning_line(&bb), None);
+    }
+
+    #[test]
+    fn test_winning_line_tie_break() {
+        let geo = BoardGeometry::<{ nw_for_board(7, 6) }>::new(7, 6);
+        let w = 7usize;
+
+        // Five in a row horizontally plus a vertical four: horizontal wins,
+        // and of the two overlapping horizontal fours the leftmost is returned.
+        let mut bb = Bitboard::empty();
+        for col in 1..6 {
+            bb.set(col);
+        }
+        for row in 0..4 {
+            bb.set(row * w + 6);
+        }
+        assert_eq!(geo.winning_line(&bb), Some(vec![1, 2, 3, 4]));
+    }
+
+    fn empty_cells<const NW: usize>(
+        geo: &BoardGeometry<NW>,
+        a: &Bitboard<NW>,
+        b: &Bitboard<NW>,
+    ) -> Bitboard<NW> {
+        geo.board_mask.andnot(*a | *b)
+    }
+
+    #[test]
+    fn test_count_open_threes_horizontal() {
+        let geo = BoardGeometry::<{ nw_for_board(7, 6) }>::new(7, 6);
+        let w = 7usize;
+
+        // Row 2, cols 2..=4: open at cols 1 and 5
+        let mut red = Bitboard::empty();
+        for col in 2..5 {
+            red.set(2 * w + col);
+        }
+        let yellow = Bitboard::empty();
+        assert_eq!(
+            geo.count_open_threes(&red, &empty_cells(&geo, &red, &yellow)),
+            2
+        );
+
+        // Against the left edge: only the right end extends
+        let mut red = Bitboard::empty();
+        for col in 0..3 {
+            red.set(2 * w + col);
+        }
+        assert_eq!(
+            geo.count_open_threes(&red, &empty_cells(&geo, &red, &yellow)),
+            1
+        );
+    }
+
+    #[test]
+    fn test_count_open_threes_no_wraparound() {
+        let geo = BoardGeometry::<{ nw_for_board(7, 6) }>::new(7, 6);
+        let w = 7usize;
+
+        // Row 0 cols 4..=6: the index after col 6 is col 0 of row 1, which must not count
+        let mut red = Bitboard::empty();
+        for col in 4..7 {
+            red.set(0 * w + col);
+        }
+        let yellow = Bitboard::empty();
+        assert_eq!(
+            geo.count_open_threes(&red, &empty_cells(&geo, &red, &yellow)),
+            1
+        );
+    }
+
+    #[test]
+    fn test_count_open_threes_diagonal() {
+        let geo = BoardGeometry::<{ nw_for_board(7, 6) }>::new(7, 6);
+        let w = 7usize;
+
+        // Ascending diagonal (1,1), (2,2), (3,3): open at (0,0) and (4,4)
+        let mut red = Bitboard::empty();
+        for i in 1..4 {
+            red.set(i * w + i);
+        }
+        let yellow = Bitboard::empty();
+        assert_eq!(
+            geo.count_open_threes(&red, &empty_cells(&geo, &red, &yellow)),
+            2
+        );
+
+        // Descending diagonal (0,3), (1,2), (2,1): (3,0) open, (-1,4) off the board
+        let mut red = Bitboard::empty();
+        for i in 0..3 {
+            red.set(i * w + (3 - i));
+        }
+        assert_eq!(
+            geo.count_open_threes(&red, &empty_cells(&geo, &red, &yellow)),
+            1
+        );
+    }
+
+    #[test]
+    fn test_count_open_threes_blocked() {
+        let geo = BoardGeometry::<{ nw_for_board(7, 6) }>::new(7, 6);
+        let w = 7usize;
+
+        // Row 0, cols 2..=4, with opponent stones at cols 1 and 5
+        let mut red = Bitboard::empty();
+        for col in 2..5 {
+            red.set(0 * w + col);
+        }
+        let mut yellow = Bitboard::empty();
+        yellow.set(0 * w + 1);
+        yellow.set(0 * w + 5);
+        assert_eq!(
+            geo.count_open_threes(&red, &empty_cells(&geo, &red, &yellow)),
+            0
+        );
+
+        // Only two in a row is not a three
+        let mut red = Bitboard::empty();
+        red.set(3 * w + 3);
+        red.set(3 * w + 4);
+        assert_eq!(
+            geo.count_open_threes(&red, &empty_cells(&geo, &red, &yellow)),
+            0
+        );
+    }
+
+    #[test]
+    fn test_has_n_in_a_row() {
+        let geo3 = BoardGeometry::<{ nw_for_board(7, 6) }>::with_connect_len(7, 6, 3);
+        let geo5 = BoardGeometry::<{ nw_for_board(7, 6) }>::with_connect_len(7, 6, 5);
+        let w = 7usize;
+
+        // Ascending diagonal of 4: (0,0)..(3,3)
+        let mut bb = Bitboard::empty();
+        for i in 0..4 {
+            bb.set(i * w + i);
+        }
+        assert!(geo3.has_n_in_a_row(&bb));
+        assert!(!geo5.has_n_in_a_row(&bb));
+        assert!(geo3.has_n_through(&bb, 0));
+        assert!(!geo5.has_n_through(&bb, 0));
+
+        bb.set(4 * w + 4);
+        assert!(geo5.has_n_in_a_row(&bb));
+        assert!(geo5.has_n_through(&bb, 2 * w + 2));
+        assert_eq!(
+            geo5.winning_line(&bb),
+            Some(vec![0, w + 1, 2 * w + 2, 3 * w + 3, 4 * w + 4])
+        );
+
+        // Row 0 cols 5,6 + row 1 col 0 must not wrap into a three
+        let mut bb = Bitboard::empty();
+        bb.set(5);
+        bb.set(6);
+        bb.set(w);
+        assert!(!geo3.has_n_in_a_row(&bb));
+    }
+
+    #[test]
+    fn test_has_n_in_a_row_agrees_with_through() {
+        for connect_len in [2u8, 3, 5, 6] {
+            let geo = BoardGeometry::<{ nw_for_board(9, 9) }>::with_connect_len(9, 9, connect_len);
+            for seed in 0..500 {
+                let bb = random_bitboard(&geo, 0.5, seed);
+                let through_any = bb.iter_ones().any(|idx| geo.has_n_through(&bb, idx));
+                assert_eq!(through_any, geo.has_n_in_a_row(&bb));
+                assert_eq!(geo.winning_line(&bb).is_some(), geo.has_n_in_a_row(&bb));
+            }
+        }
+    }
 }
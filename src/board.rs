@@ -1,20 +1,145 @@
-use std::fmt;
-use std::hash::{Hash, Hasher};
-
-use crate::bitboard::{nw_for_board, Bitboard, BoardGeometry};
-use crate::limits::assert_valid_board_dimensions;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+
+use crate::bitboard::{mirror_index_horizontal, nw_for_board, Bitboard, BoardGeometry};
+use crate::limits::{
+    assert_valid_board_dimensions, board_dimension_is_valid, MAX_BOARD_DIMENSION,
+    MIN_BOARD_DIMENSION,
+};
 use crate::player::Player;
 use crate::position::Position;
+use crate::zobrist;
 
 pub const STANDARD_COLS: u8 = 7;
 pub const STANDARD_ROWS: u8 = 6;
 
+/// Error returned by `Board::from_fen`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ParseError {
+    /// The requested dimensions are out of range or do not match `NW`.
+    InvalidDimensions { width: u8, height: u8 },
+    /// A character other than a digit, `r`/`y`, or `/` was found.
+    InvalidCharacter(char),
+    /// The number of `/`-separated rows differs from the board height.
+    WrongRowCount { expected: u8, found: usize },
+    /// A row (counted from the top, starting at 0) does not span the board width.
+    WrongRowLength {
+        row: usize,
+        expected: u8,
+        found: usize,
+    },
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::InvalidDimensions { width, height } => write!(
+                f,
+                "Invalid board dimensions {}x{} (each must be between {} and {})",
+                width, height, MIN_BOARD_DIMENSION, MAX_BOARD_DIMENSION
+            ),
+            ParseError::InvalidCharacter(c) => write!(f, "Invalid character '{}' in FEN", c),
+            ParseError::WrongRowCount { expected, found } => {
+                write!(f, "Expected {} rows in FEN, found {}", expected, found)
+            }
+            ParseError::WrongRowLength {
+                row,
+                expected,
+                found,
+            } => write!(
+                f,
+                "Row {} of FEN spans {} cells, expected {}",
+                row, found, expected
+            ),
+        }
+    }
+}
+
+impl core::error::Error for ParseError {}
+
+/// Error returned by `Board::from_bytes`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum BytesError {
+    /// The requested dimensions are out of range or do not match `NW`.
+    InvalidDimensions { width: u8, height: u8 },
+    /// The input is not `ceil(width * height / 4)` bytes long.
+    WrongLength { expected: usize, found: usize },
+    /// A cell holds the unused code `0b11`.
+    InvalidCell(usize),
+    /// Bits past the last cell in the final byte are set, so the encoding is
+    /// not the one `to_bytes` would produce.
+    NonZeroPadding,
+}
+
+impl fmt::Display for BytesError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BytesError::InvalidDimensions { width, height } => write!(
+                f,
+                "Invalid board dimensions {}x{} (each must be between {} and {})",
+                width, height, MIN_BOARD_DIMENSION, MAX_BOARD_DIMENSION
+            ),
+            BytesError::WrongLength { expected, found } => {
+                write!(f, "Expected {} bytes, found {}", expected, found)
+            }
+            BytesError::InvalidCell(idx) => write!(f, "Invalid code for cell {}", idx),
+            BytesError::NonZeroPadding => write!(f, "Padding bits after the last cell are set"),
+        }
+    }
+}
+
+impl core::error::Error for BytesError {}
+
+/// Error returned by `Board::place_column_stack`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum StackError {
+    /// The column does not exist on this board.
+    ColumnOutOfRange(u8),
+    /// The column has room for only `free` more stones.
+    Overflow { col: u8, free: u8, requested: usize },
+}
+
+impl fmt::Display for StackError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StackError::ColumnOutOfRange(col) => write!(f, "Column {} is off the board", col),
+            StackError::Overflow {
+                col,
+                free,
+                requested,
+            } => write!(
+                f,
+                "Column {} has room for {} stones, not {}",
+                col, free, requested
+            ),
+        }
+    }
+}
+
+impl core::error::Error for StackError {}
+
+/// 2-bit cell codes used by `Board::to_bytes`.
+const CELL_EMPTY: u8 = 0b00;
+const CELL_RED: u8 = 0b01;
+const CELL_YELLOW: u8 = 0b10;
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(into = "BoardData<NW>", try_from = "BoardData<NW>")
+)]
 pub struct Board<const NW: usize> {
     red: Bitboard<NW>,
     yellow: Bitboard<NW>,
     width: u8,
     height: u8,
+    /// Number of stones in each column, kept in sync with the bitboards.
+    /// Only indices 0..width are used.
+    heights: [u8; MAX_BOARD_DIMENSION as usize],
 }
 
 impl<const NW: usize> Hash for Board<NW> {
@@ -28,6 +153,8 @@ impl<const NW: usize> Hash for Board<NW> {
 
 impl<const NW: usize> Board<NW> {
     /// Create a board with dimensions in `4..=32`.
+    ///
+    /// Panics if a dimension is out of range or `NW` does not match the board.
     /// `NW` must match `ceil(width * height / 64)`.
     pub fn new(width: u8, height: u8) -> Self {
         assert_valid_board_dimensions(width, height);
@@ -45,9 +172,35 @@ impl<const NW: usize> Board<NW> {
             yellow: Bitboard::empty(),
             width,
             height,
+            heights: [0; MAX_BOARD_DIMENSION as usize],
         }
     }
 
+    /// Build a board from raw bitboards, computing column heights.
+    /// Caller must ensure dimensions match `NW` and the bitboards are disjoint.
+    fn from_stones(red: Bitboard<NW>, yellow: Bitboard<NW>, width: u8, height: u8) -> Self {
+        let mut board = Board {
+            red,
+            yellow,
+            width,
+            height,
+            heights: [0; MAX_BOARD_DIMENSION as usize],
+        };
+        for col in 0..width {
+            board.recompute_height(col);
+        }
+        board
+    }
+
+    /// Recount the stones in column `col`.
+    fn recompute_height(&mut self, col: u8) {
+        let occupied = self.occupied();
+        let w = self.width as usize;
+        self.heights[col as usize] = (0..self.height as usize)
+            .filter(|&row| occupied.get(row * w + col as usize))
+            .count() as u8;
+    }
+
     pub fn width(&self) -> u8 {
         self.width
     }
@@ -81,12 +234,56 @@ impl<const NW: usize> Board<NW> {
                 Some(Player::Yellow) => self.yellow.set(idx),
                 None => {}
             }
+            self.recompute_height(pos.col);
         }
     }
 
     pub fn clear(&mut self) {
         self.red = Bitboard::empty();
         self.yellow = Bitboard::empty();
+        self.heights = [0; MAX_BOARD_DIMENSION as usize];
+    }
+
+    /// Bitboard of Red's stones; bit `row * width + col` is set for each stone.
+    pub fn red_bits(&self) -> Bitboard<NW> {
+        self.red
+    }
+
+    /// Bitboard of Yellow's stones, in the same layout as `red_bits`.
+    pub fn yellow_bits(&self) -> Bitboard<NW> {
+        self.yellow
+    }
+
+    /// Bitboard of all occupied cells.
+    pub fn occupied_bits(&self) -> Bitboard<NW> {
+        self.occupied()
+    }
+
+    /// Occupied cells with their owner: every Red stone, then every Yellow
+    /// stone, each in ascending cell index (row by row from the bottom, left
+    /// to right within a row).
+    pub fn iter_pieces(&self) -> impl Iterator<Item = (Position, Player)> + '_ {
+        let width = self.width;
+        Player::iter().flat_map(move |player| {
+            self.stones_for(player)
+                .iter_ones()
+                .map(move |idx| (Position::from_index(idx, width), player))
+        })
+    }
+
+    /// Cells whose contents differ between `self` and `other`, as
+    /// `(position, before, after)` with `self` as the before state, in
+    /// ascending cell index. Both boards must have the same dimensions.
+    pub fn diff(&self, other: &Board<NW>) -> Vec<(Position, Option<Player>, Option<Player>)> {
+        debug_assert_eq!((self.width, self.height), (other.width, other.height));
+        let changed = (self.red ^ other.red) | (self.yellow ^ other.yellow);
+        changed
+            .iter_ones()
+            .map(|idx| {
+                let pos = Position::from_index(idx, self.width);
+                (pos, self.get_piece(&pos), other.get_piece(&pos))
+            })
+            .collect()
     }
 
     #[inline]
@@ -109,6 +306,7 @@ impl<const NW: usize> Board<NW> {
             Player::Red => self.red.set(idx),
             Player::Yellow => self.yellow.set(idx),
         }
+        self.heights[idx % self.width as usize] += 1;
     }
 
     /// Drop a piece into the given column. Returns the row it landed on, or None if column is full.
@@ -132,13 +330,64 @@ impl<const NW: usize> Board<NW> {
         }
     }
 
-    /// Get the number of pieces in a column.
-    pub fn column_height(&self, col: u8, geo: &BoardGeometry<NW>) -> u8 {
+    /// Drop the stones in `stack` into column `col`, bottom-up, as repeated
+    /// `drop_piece` calls would. Fails without changing the board if the
+    /// column does not exist or cannot hold them all. No win is checked.
+    pub fn place_column_stack(
+        &mut self,
+        col: u8,
+        stack: &[Player],
+        geo: &BoardGeometry<NW>,
+    ) -> Result<(), StackError> {
+        if col >= self.width {
+            return Err(StackError::ColumnOutOfRange(col));
+        }
+        let free = geo.column_masks[col as usize]
+            .andnot(self.occupied())
+            .count() as u8;
+        if stack.len() > free as usize {
+            return Err(StackError::Overflow {
+                col,
+                free,
+                requested: stack.len(),
+            });
+        }
+
+        for &player in stack {
+            self.drop_piece(col, player, geo);
+        }
+        Ok(())
+    }
+
+    /// Like `drop_piece`, but also report whether the drop completed
+    /// `geo.connect_len` in a row. Only lines through the landing cell are
+    /// checked, so no full-board scan is needed.
+    pub fn drop_and_check(
+        &mut self,
+        col: u8,
+        player: Player,
+        geo: &BoardGeometry<NW>,
+    ) -> Option<(u8, bool)> {
+        let row = self.drop_piece(col, player, geo)?;
+        let idx = Position::new(col, row).to_index(self.width);
+        Some((row, geo.has_n_through(&self.stones_for(player), idx)))
+    }
+
+    /// Get the number of pieces in a column. O(1): heights are maintained
+    /// incrementally, so `_geo` is unused and kept for API compatibility.
+    pub fn column_height(&self, col: u8, _geo: &BoardGeometry<NW>) -> u8 {
         let col_usize = col as usize;
         if col_usize >= self.width as usize {
             return 0;
         }
-        (self.occupied() & geo.column_masks[col_usize]).count() as u8
+        self.heights[col_usize]
+    }
+
+    /// Stack height of every column at once, from the incrementally maintained
+    /// heights. Entries `0..width` are valid; the rest are always zero. `_geo`
+    /// is unused, as in [`Board::column_height`].
+    pub fn height_profile(&self, _geo: &BoardGeometry<NW>) -> [u8; MAX_BOARD_DIMENSION as usize] {
+        self.heights
     }
 
     /// Check if a column is full.
@@ -147,9 +396,30 @@ impl<const NW: usize> Board<NW> {
         if col_usize >= self.width as usize {
             return true;
         }
-        // Check if the top cell in this column is occupied
-        let top_idx = (self.height as usize - 1) * self.width as usize + col_usize;
-        self.occupied().get(top_idx)
+        self.heights[col_usize] >= self.height
+    }
+
+    /// The cell a stone dropped into each non-full column would land on.
+    pub fn playable_cells(&self) -> Bitboard<NW> {
+        let w = self.width as usize;
+        let mut cells = Bitboard::empty();
+        for col in 0..w {
+            let height = self.heights[col];
+            if height < self.height {
+                cells.set(height as usize * w + col);
+            }
+        }
+        cells
+    }
+
+    /// Number of stones `player` has on the board.
+    pub fn count(&self, player: Player) -> u32 {
+        self.stones_for(player).count()
+    }
+
+    /// Number of stones on the board.
+    pub fn total_count(&self) -> u32 {
+        self.red.count() + self.yellow.count()
     }
 
     /// Check if the board is completely full.
@@ -157,9 +427,311 @@ impl<const NW: usize> Board<NW> {
         (self.occupied() & geo.top_row_mask) == geo.top_row_mask
     }
 
-    /// Check if the given player has won.
+    /// Check if the given player has won (`geo.connect_len` in a row).
     pub fn check_win(&self, player: Player, geo: &BoardGeometry<NW>) -> bool {
-        geo.has_four_in_a_row(&self.stones_for(player))
+        geo.has_n_in_a_row(&self.stones_for(player))
+    }
+
+    /// The board reflected left-to-right (column `c` becomes `width - 1 - c`).
+    pub fn mirror_horizontal(&self) -> Self {
+        let w = self.width as usize;
+        let mirror = |bb: Bitboard<NW>| {
+            let mut out = Bitboard::empty();
+            for idx in bb.iter_ones() {
+                out.set(mirror_index_horizontal(idx, w));
+            }
+            out
+        };
+
+        Board::from_stones(
+            mirror(self.red),
+            mirror(self.yellow),
+            self.width,
+            self.height,
+        )
+    }
+
+    /// The board with every Red stone made Yellow and vice versa.
+    pub fn swap_colors(&self) -> Self {
+        Board {
+            red: self.yellow,
+            yellow: self.red,
+            ..*self
+        }
+    }
+
+    /// Canonical representative of this board under horizontal mirroring, the
+    /// only symmetry that preserves gravity. Returns the smaller of the board and
+    /// its mirror, comparing Red's then Yellow's bitboard words from word 0
+    /// upward, and whether the mirror was chosen. A symmetric board is returned
+    /// unmirrored.
+    pub fn canonical(&self) -> (Self, bool) {
+        let mirrored = self.mirror_horizontal();
+        let key = |b: &Board<NW>| (*b.red.words(), *b.yellow.words());
+        if key(&mirrored) < key(self) {
+            (mirrored, true)
+        } else {
+            (*self, false)
+        }
+    }
+
+    /// The board rotated by 180°, so the bottom-left cell becomes the top-right.
+    ///
+    /// Useful as a board transform for deduplicating positions, but unlike
+    /// `mirror_horizontal` it is not a symmetry of play: stones end up hanging
+    /// from the top row, which gravity would not allow.
+    pub fn rotate_180(&self) -> Self {
+        let area = self.width as u16 * self.height as u16;
+        Board::from_stones(
+            self.red.reverse_bits_within(area),
+            self.yellow.reverse_bits_within(area),
+            self.width,
+            self.height,
+        )
+    }
+
+    /// Zobrist key of this board with `turn` to move.
+    ///
+    /// Built from the fixed table in `crate::zobrist`, so keys are stable across
+    /// runs. The key can be updated incrementally: XOR `zobrist::piece_key` to
+    /// toggle a stone and `zobrist::SIDE_TO_MOVE_KEY` to switch sides.
+    pub fn zobrist_key(&self, turn: Player) -> u64 {
+        let mut key = match turn {
+            Player::Red => 0,
+            Player::Yellow => zobrist::SIDE_TO_MOVE_KEY,
+        };
+        for idx in self.red.iter_ones() {
+            key ^= zobrist::piece_key(Player::Red, idx);
+        }
+        for idx in self.yellow.iter_ones() {
+            key ^= zobrist::piece_key(Player::Yellow, idx);
+        }
+        key
+    }
+
+    /// Hash of this board with `turn` to move that is identical across runs,
+    /// builds and processes, unlike `Hash` through std's per-process seeded
+    /// `RandomState`. It is the Zobrist key with the board dimensions folded
+    /// in, so the same stones on boards of different sizes hash differently.
+    pub fn stable_hash(&self, turn: Player) -> u64 {
+        self.zobrist_key(turn) ^ zobrist::dimensions_key(self.width, self.height)
+    }
+
+    /// Encode the board as a FEN-like string.
+    ///
+    /// Rows are listed from top to bottom separated by `/`. Within a row, `r`
+    /// and `y` are stones and a number is a run of that many empty cells, e.g.
+    /// `7/7/7/7/7/3r3` for a standard board with one red stone in the centre.
+    pub fn to_fen(&self) -> String {
+        let mut fen = String::new();
+
+        for row in (0..self.height).rev() {
+            let mut empty_run = 0;
+            for col in 0..self.width {
+                match self.get_piece(&Position::new(col, row)) {
+                    Some(player) => {
+                        if empty_run > 0 {
+                            fen.push_str(&empty_run.to_string());
+                            empty_run = 0;
+                        }
+                        fen.push(player.to_char().to_ascii_lowercase());
+                    }
+                    None => empty_run += 1,
+                }
+            }
+            if empty_run > 0 {
+                fen.push_str(&empty_run.to_string());
+            }
+            if row > 0 {
+                fen.push('/');
+            }
+        }
+
+        fen
+    }
+
+    /// Pack the board at 2 bits per cell: `00` empty, `01` Red, `10` Yellow.
+    /// Cells are taken in row-major order from the bottom row, four per byte
+    /// starting at the least significant bits, and the output is
+    /// `ceil(width * height / 4)` bytes long. Dimensions are not stored.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let area = self.width as usize * self.height as usize;
+        let mut bytes = vec![0u8; area.div_ceil(4)];
+        for idx in 0..area {
+            let code = if self.red.get(idx) {
+                CELL_RED
+            } else if self.yellow.get(idx) {
+                CELL_YELLOW
+            } else {
+                CELL_EMPTY
+            };
+            bytes[idx / 4] |= code << ((idx % 4) * 2);
+        }
+        bytes
+    }
+
+    /// Unpack a board written by `to_bytes`. No gravity check is made.
+    pub fn from_bytes(bytes: &[u8], width: u8, height: u8) -> Result<Self, BytesError> {
+        if !board_dimension_is_valid(width)
+            || !board_dimension_is_valid(height)
+            || nw_for_board(width, height) != NW
+        {
+            return Err(BytesError::InvalidDimensions { width, height });
+        }
+
+        let area = width as usize * height as usize;
+        let expected = area.div_ceil(4);
+        if bytes.len() != expected {
+            return Err(BytesError::WrongLength {
+                expected,
+                found: bytes.len(),
+            });
+        }
+        let used_bits = (area % 4) * 2;
+        if used_bits != 0 && bytes[expected - 1] >> used_bits != 0 {
+            return Err(BytesError::NonZeroPadding);
+        }
+
+        let mut board = Board::new(width, height);
+        for idx in 0..area {
+            match (bytes[idx / 4] >> ((idx % 4) * 2)) & 0b11 {
+                CELL_EMPTY => {}
+                CELL_RED => board.set_bit(idx, Player::Red),
+                CELL_YELLOW => board.set_bit(idx, Player::Yellow),
+                _ => return Err(BytesError::InvalidCell(idx)),
+            }
+        }
+        Ok(board)
+    }
+
+    /// Parse a board from the format produced by `to_fen`. Stones may be
+    /// upper- or lowercase. No gravity check is made.
+    pub fn from_fen(s: &str, width: u8, height: u8) -> Result<Self, ParseError> {
+        if !board_dimension_is_valid(width)
+            || !board_dimension_is_valid(height)
+            || nw_for_board(width, height) != NW
+        {
+            return Err(ParseError::InvalidDimensions { width, height });
+        }
+
+        let rows: Vec<&str> = s.split('/').collect();
+        if rows.len() != height as usize {
+            return Err(ParseError::WrongRowCount {
+                expected: height,
+                found: rows.len(),
+            });
+        }
+
+        let mut board = Board::new(width, height);
+
+        for (i, row_str) in rows.iter().enumerate() {
+            let row = height - 1 - i as u8;
+            let mut col = 0usize;
+            let mut chars = row_str.chars().peekable();
+
+            while let Some(c) = chars.next() {
+                if let Some(digit) = c.to_digit(10) {
+                    let mut run = digit as usize;
+                    while let Some(next) = chars.peek().and_then(|c| c.to_digit(10)) {
+                        run = run.saturating_mul(10).saturating_add(next as usize);
+                        chars.next();
+                    }
+                    col = col.saturating_add(run);
+                } else if let Some(player) = Player::from_char(c) {
+                    if col < width as usize {
+                        board.set_bit(Position::new(col as u8, row).to_index(width), player);
+                    }
+                    col += 1;
+                } else {
+                    return Err(ParseError::InvalidCharacter(c));
+                }
+
+                if col > width as usize {
+                    break;
+                }
+            }
+
+            if col != width as usize {
+                return Err(ParseError::WrongRowLength {
+                    row: i,
+                    expected: width,
+                    found: col,
+                });
+            }
+        }
+
+        Ok(board)
+    }
+}
+
+/// Serialized form of a `Board`. Deserialization goes through `TryFrom` so that
+/// the dimensions can be checked against `NW` instead of panicking.
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct BoardData<const NW: usize> {
+    width: u8,
+    height: u8,
+    red: Bitboard<NW>,
+    yellow: Bitboard<NW>,
+}
+
+#[cfg(feature = "serde")]
+impl<const NW: usize> From<Board<NW>> for BoardData<NW> {
+    fn from(board: Board<NW>) -> Self {
+        BoardData {
+            width: board.width,
+            height: board.height,
+            red: board.red,
+            yellow: board.yellow,
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<const NW: usize> TryFrom<BoardData<NW>> for Board<NW> {
+    type Error = String;
+
+    fn try_from(data: BoardData<NW>) -> Result<Self, Self::Error> {
+        if !board_dimension_is_valid(data.width) || !board_dimension_is_valid(data.height) {
+            return Err(alloc::format!(
+                "Board dimensions {}x{} must be between {} and {}",
+                data.width,
+                data.height,
+                MIN_BOARD_DIMENSION,
+                MAX_BOARD_DIMENSION
+            ));
+        }
+
+        let required_words = nw_for_board(data.width, data.height);
+        if NW != required_words {
+            return Err(alloc::format!(
+                "NW={} does not match board {}x{} (need {})",
+                NW,
+                data.width,
+                data.height,
+                required_words
+            ));
+        }
+
+        if (data.red & data.yellow).is_nonzero() {
+            return Err("Red and yellow stones overlap".to_string());
+        }
+
+        let geo = BoardGeometry::<NW>::new(data.width, data.height);
+        if (data.red | data.yellow).andnot(geo.board_mask).is_nonzero() {
+            return Err(alloc::format!(
+                "Stones lie outside the {}x{} board",
+                data.width,
+                data.height
+            ));
+        }
+
+        Ok(Board::from_stones(
+            data.red,
+            data.yellow,
+            data.width,
+            data.height,
+        ))
     }
 }
 
@@ -208,6 +780,40 @@ mod tests {
         BoardGeometry::new(STANDARD_COLS, STANDARD_ROWS)
     }
 
+    #[test]
+    fn test_bit_accessors_match_get_piece() {
+        let geo = make_geo();
+        let mut board = Board::<{ nw_for_board(STANDARD_COLS, STANDARD_ROWS) }>::new(
+            STANDARD_COLS,
+            STANDARD_ROWS,
+        );
+        for (col, player) in [(3, Player::Red), (3, Player::Yellow), (0, Player::Red)] {
+            board
+                .drop_piece(col, player, &geo)
+                .expect("test_bit_accessors_match_get_piece: column full");
+        }
+
+        assert_eq!(
+            board.occupied_bits(),
+            board.red_bits() | board.yellow_bits()
+        );
+        assert_eq!(board.occupied_bits().words()[0], (1 << 3) | (1 << 10) | 1);
+        for row in 0..STANDARD_ROWS {
+            for col in 0..STANDARD_COLS {
+                let pos = Position::new(col, row);
+                let idx = pos.to_index(STANDARD_COLS);
+                assert_eq!(
+                    board.get_piece(&pos) == Some(Player::Red),
+                    board.red_bits().get(idx)
+                );
+                assert_eq!(
+                    board.get_piece(&pos) == Some(Player::Yellow),
+                    board.yellow_bits().get(idx)
+                );
+            }
+        }
+    }
+
     #[test]
     fn test_empty_board_creation() {
         let geo = make_geo();
@@ -222,6 +828,28 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_board_boundary_sizes() {
+        let geo = BoardGeometry::<{ nw_for_board(32, 32) }>::new(32, 32);
+        let mut board = Board::<{ nw_for_board(32, 32) }>::new(32, 32);
+        for _ in 0..32 {
+            board.drop_piece(31, Player::Red, &geo);
+        }
+        assert!(board.is_column_full(31));
+        assert_eq!(board.column_height(31, &geo), 32);
+        assert_eq!(board.drop_piece(31, Player::Red, &geo), None);
+        assert!(!board.is_board_full(&geo));
+
+        let board = Board::<{ nw_for_board(4, 4) }>::new(4, 4);
+        assert_eq!(board.width(), 4);
+    }
+
+    #[test]
+    #[should_panic(expected = "Board width must be between 4 and 32")]
+    fn test_board_too_large() {
+        Board::<{ nw_for_board(40, 40) }>::new(40, 40);
+    }
+
     #[test]
     fn test_empty_board_creation_10x10() {
         let geo = BoardGeometry::<{ nw_for_board(10, 10) }>::new(10, 10);
@@ -247,6 +875,40 @@ mod tests {
         assert_eq!(board.get_piece(&pos), Some(Player::Red));
     }
 
+    #[test]
+    fn test_drop_and_check_matches_check_win() {
+        use rand::{RngExt, SeedableRng};
+
+        let geo = make_geo();
+        let mut board = Board::<{ nw_for_board(STANDARD_COLS, STANDARD_ROWS) }>::default();
+        let mut rng = rand::rngs::SmallRng::seed_from_u64(23);
+        let mut player = Player::Red;
+        let mut wins = 0;
+
+        for _ in 0..5_000 {
+            let col = rng.random_range(0..STANDARD_COLS);
+            let mut expected_board = board;
+            let expected_row = expected_board.drop_piece(col, player, &geo);
+
+            let result = board.drop_and_check(col, player, &geo);
+            assert_eq!(result.map(|(row, _)| row), expected_row);
+            assert_eq!(board, expected_board);
+            let Some((_, won)) = result else {
+                continue;
+            };
+            assert_eq!(won, board.check_win(player, &geo));
+
+            if won || board.is_board_full(&geo) {
+                wins += won as u32;
+                board.clear();
+                player = Player::Red;
+            } else {
+                player = player.opposite();
+            }
+        }
+        assert!(wins > 0);
+    }
+
     #[test]
     fn test_drop_multiple_pieces() {
         let geo = make_geo();
@@ -441,9 +1103,551 @@ mod tests {
         let size_7x6 = std::mem::size_of::<Board<{ nw_for_board(7, 6) }>>();
         let size_9x9 = std::mem::size_of::<Board<{ nw_for_board(9, 9) }>>();
 
-        // 7x6 (NW=1): 2*8 + 2 = 18 bytes + padding
-        assert!(size_7x6 <= 24, "7x6 Board too large: {}", size_7x6);
-        // 9x9 (NW=2): 2*16 + 2 = 34 bytes + padding
-        assert!(size_9x9 <= 40, "9x9 Board too large: {}", size_9x9);
+        // 7x6 (NW=1): 2*8 + 2 + 32 (column heights) = 50 bytes + padding
+        assert!(size_7x6 <= 56, "7x6 Board too large: {}", size_7x6);
+        // 9x9 (NW=2): 2*16 + 2 + 32 (column heights) = 66 bytes + padding
+        assert!(size_9x9 <= 72, "9x9 Board too large: {}", size_9x9);
+    }
+
+    #[test]
+    fn test_fen_empty() {
+        let board = Board::<{ nw_for_board(STANDARD_COLS, STANDARD_ROWS) }>::default();
+        assert_eq!(board.to_fen(), "7/7/7/7/7/7");
+        assert_eq!(
+            Board::from_fen("7/7/7/7/7/7", STANDARD_COLS, STANDARD_ROWS),
+            Ok(board)
+        );
+    }
+
+    #[test]
+    fn test_fen_fixture() {
+        let board = Board::<{ nw_for_board(STANDARD_COLS, STANDARD_ROWS) }>::from_fen(
+            "7/7/7/7/3y3/2ryr1Y",
+            STANDARD_COLS,
+            STANDARD_ROWS,
+        )
+        .expect("test_fen_fixture: failed to parse");
+
+        assert_eq!(board.get_piece(&Position::new(2, 0)), Some(Player::Red));
+        assert_eq!(board.get_piece(&Position::new(3, 0)), Some(Player::Yellow));
+        assert_eq!(board.get_piece(&Position::new(4, 0)), Some(Player::Red));
+        assert_eq!(board.get_piece(&Position::new(6, 0)), Some(Player::Yellow));
+        assert_eq!(board.get_piece(&Position::new(3, 1)), Some(Player::Yellow));
+        assert_eq!(board.get_piece(&Position::new(0, 0)), None);
+        assert_eq!(board.to_fen(), "7/7/7/7/3y3/2ryr1y");
+    }
+
+    #[test]
+    fn test_fen_multi_digit_runs() {
+        let mut board = Board::<{ nw_for_board(12, 4) }>::new(12, 4);
+        board.set_piece(&Position::new(11, 0), Some(Player::Red));
+
+        assert_eq!(board.to_fen(), "12/12/12/11r");
+        assert_eq!(Board::from_fen("12/12/12/11r", 12, 4), Ok(board));
+    }
+
+    #[test]
+    fn test_fen_round_trip() {
+        let geo = make_geo();
+        let mut board = Board::<{ nw_for_board(STANDARD_COLS, STANDARD_ROWS) }>::default();
+        let cols = [3u8, 3, 2, 4, 4, 0, 6, 6, 6, 1, 5, 3];
+
+        for (i, &col) in cols.iter().enumerate() {
+            let player = if i % 2 == 0 {
+                Player::Red
+            } else {
+                Player::Yellow
+            };
+            board.drop_piece(col, player, &geo);
+
+            let fen = board.to_fen();
+            assert_eq!(
+                Board::from_fen(&fen, STANDARD_COLS, STANDARD_ROWS),
+                Ok(board)
+            );
+        }
+    }
+
+    #[test]
+    fn test_fen_errors() {
+        type StdBoard = Board<{ nw_for_board(STANDARD_COLS, STANDARD_ROWS) }>;
+
+        assert_eq!(
+            StdBoard::from_fen("7/7/7/7/7", 7, 6),
+            Err(ParseError::WrongRowCount {
+                expected: 6,
+                found: 5
+            })
+        );
+        assert_eq!(
+            StdBoard::from_fen("7/7/7/7/7/6", 7, 6),
+            Err(ParseError::WrongRowLength {
+                row: 5,
+                expected: 7,
+                found: 6
+            })
+        );
+        assert_eq!(
+            StdBoard::from_fen("7/7/7/7/7/4rrry", 7, 6),
+            Err(ParseError::WrongRowLength {
+                row: 5,
+                expected: 7,
+                found: 8
+            })
+        );
+        assert_eq!(
+            StdBoard::from_fen("7/7/7/7/7/3x3", 7, 6),
+            Err(ParseError::InvalidCharacter('x'))
+        );
+        assert_eq!(
+            StdBoard::from_fen("8/8/8/8/8/8/8/8/8", 8, 9),
+            Err(ParseError::InvalidDimensions {
+                width: 8,
+                height: 9
+            })
+        );
+        assert_eq!(
+            StdBoard::from_fen("3/3/3", 3, 3),
+            Err(ParseError::InvalidDimensions {
+                width: 3,
+                height: 3
+            })
+        );
+    }
+
+    #[test]
+    fn test_zobrist_key_transposition() {
+        let geo = make_geo();
+        let mut a = Board::<{ nw_for_board(STANDARD_COLS, STANDARD_ROWS) }>::default();
+        let mut b = a;
+
+        // Same stones, different move orders
+        a.drop_piece(3, Player::Red, &geo);
+        a.drop_piece(4, Player::Yellow, &geo);
+        a.drop_piece(2, Player::Red, &geo);
+        b.drop_piece(2, Player::Red, &geo);
+        b.drop_piece(4, Player::Yellow, &geo);
+        b.drop_piece(3, Player::Red, &geo);
+
+        assert_eq!(a.zobrist_key(Player::Yellow), b.zobrist_key(Player::Yellow));
+        assert_ne!(a.zobrist_key(Player::Yellow), a.zobrist_key(Player::Red));
+    }
+
+    #[test]
+    fn test_stable_hash() {
+        let geo = make_geo();
+        let mut a = Board::<{ nw_for_board(STANDARD_COLS, STANDARD_ROWS) }>::default();
+        let mut b = Board::<{ nw_for_board(STANDARD_COLS, STANDARD_ROWS) }>::default();
+        for (board, cols) in [(&mut a, [3, 4, 2]), (&mut b, [2, 4, 3])] {
+            for (i, col) in cols.into_iter().enumerate() {
+                let player = if i % 2 == 0 {
+                    Player::Red
+                } else {
+                    Player::Yellow
+                };
+                board.drop_piece(col, player, &geo);
+            }
+        }
+        assert_eq!(a.stable_hash(Player::Yellow), b.stable_hash(Player::Yellow));
+        assert_ne!(a.stable_hash(Player::Yellow), a.stable_hash(Player::Red));
+
+        // The empty board's hash is pinned so changes to it are deliberate.
+        let empty = Board::<{ nw_for_board(STANDARD_COLS, STANDARD_ROWS) }>::default();
+        assert_eq!(empty.stable_hash(Player::Red), 0x5401_36EE_551B_D0AB);
+
+        // Same cell indices on a wider board must not collide.
+        let wide_geo = BoardGeometry::<{ nw_for_board(8, 6) }>::new(8, 6);
+        let mut wide = Board::<{ nw_for_board(8, 6) }>::new(8, 6);
+        let mut narrow = Board::<{ nw_for_board(STANDARD_COLS, STANDARD_ROWS) }>::default();
+        wide.drop_piece(0, Player::Red, &wide_geo);
+        narrow.drop_piece(0, Player::Red, &geo);
+        assert_eq!(
+            wide.zobrist_key(Player::Yellow),
+            narrow.zobrist_key(Player::Yellow)
+        );
+        assert_ne!(
+            wide.stable_hash(Player::Yellow),
+            narrow.stable_hash(Player::Yellow)
+        );
+    }
+
+    #[test]
+    fn test_zobrist_key_incremental() {
+        let geo = make_geo();
+        let mut board = Board::<{ nw_for_board(STANDARD_COLS, STANDARD_ROWS) }>::default();
+        assert_eq!(board.zobrist_key(Player::Red), 0);
+
+        let mut key = board.zobrist_key(Player::Red);
+        for (i, &col) in [3u8, 3, 4, 0, 6].iter().enumerate() {
+            let player = if i % 2 == 0 {
+                Player::Red
+            } else {
+                Player::Yellow
+            };
+            let row = board
+                .drop_piece(col, player, &geo)
+                .expect("test_zobrist_key_incremental: column full");
+            key ^= zobrist::piece_key(player, Position::new(col, row).to_index(STANDARD_COLS));
+            key ^= zobrist::SIDE_TO_MOVE_KEY;
+            assert_eq!(key, board.zobrist_key(player.opposite()));
+        }
+    }
+
+    #[test]
+    fn test_mirror_horizontal() {
+        let geo = make_geo();
+        let mut board = Board::<{ nw_for_board(STANDARD_COLS, STANDARD_ROWS) }>::default();
+        board.drop_piece(0, Player::Red, &geo);
+        board.drop_piece(0, Player::Yellow, &geo);
+        board.drop_piece(2, Player::Red, &geo);
+
+        let mirrored = board.mirror_horizontal();
+        assert_eq!(mirrored.get_piece(&Position::new(6, 0)), Some(Player::Red));
+        assert_eq!(
+            mirrored.get_piece(&Position::new(6, 1)),
+            Some(Player::Yellow)
+        );
+        assert_eq!(mirrored.get_piece(&Position::new(4, 0)), Some(Player::Red));
+        assert_eq!(mirrored.get_piece(&Position::new(0, 0)), None);
+        assert_eq!(mirrored.mirror_horizontal(), board);
+    }
+
+    #[test]
+    fn test_canonical() {
+        let geo = make_geo();
+        let mut board = Board::<{ nw_for_board(STANDARD_COLS, STANDARD_ROWS) }>::default();
+        board.drop_piece(1, Player::Red, &geo);
+        board.drop_piece(4, Player::Yellow, &geo);
+        board.drop_piece(1, Player::Red, &geo);
+        let mirrored = board.mirror_horizontal();
+
+        let (canonical, flipped) = board.canonical();
+        let (mirrored_canonical, mirrored_flipped) = mirrored.canonical();
+        assert_eq!(canonical, mirrored_canonical);
+        assert_ne!(flipped, mirrored_flipped);
+        assert_eq!(canonical, if flipped { mirrored } else { board });
+
+        // A symmetric board is its own canonical form
+        let mut symmetric = Board::<{ nw_for_board(STANDARD_COLS, STANDARD_ROWS) }>::default();
+        symmetric.drop_piece(3, Player::Red, &geo);
+        symmetric.drop_piece(3, Player::Yellow, &geo);
+        assert_eq!(symmetric.canonical(), (symmetric, false));
+    }
+
+    #[test]
+    fn test_swap_colors() {
+        let geo = make_geo();
+        let mut board = Board::<{ nw_for_board(STANDARD_COLS, STANDARD_ROWS) }>::default();
+        board.drop_piece(2, Player::Red, &geo);
+        board.drop_piece(2, Player::Yellow, &geo);
+        board.drop_piece(5, Player::Red, &geo);
+
+        let swapped = board.swap_colors();
+        assert_eq!(swapped.red_bits(), board.yellow_bits());
+        assert_eq!(swapped.yellow_bits(), board.red_bits());
+        assert_eq!(swapped.column_height(2, &geo), 2);
+        assert_eq!(swapped.swap_colors(), board);
+    }
+
+    #[test]
+    fn test_rotate_180() {
+        let geo = BoardGeometry::<{ nw_for_board(9, 9) }>::new(9, 9);
+        let mut board = Board::<{ nw_for_board(9, 9) }>::new(9, 9);
+        for (col, player) in [
+            (0, Player::Red),
+            (0, Player::Yellow),
+            (4, Player::Red),
+            (8, Player::Yellow),
+        ] {
+            board.drop_piece(col, player, &geo);
+        }
+
+        let rotated = board.rotate_180();
+        for row in 0..9 {
+            for col in 0..9 {
+                assert_eq!(
+                    rotated.get_piece(&Position::new(8 - col, 8 - row)),
+                    board.get_piece(&Position::new(col, row))
+                );
+            }
+        }
+        assert_eq!(rotated.rotate_180(), board);
+    }
+
+    #[test]
+    fn test_mirror_horizontal_preserves_win() {
+        let geo = BoardGeometry::<{ nw_for_board(9, 9) }>::new(9, 9);
+        let mut board = Board::<{ nw_for_board(9, 9) }>::new(9, 9);
+
+        // Ascending diagonal from (0,0) to (3,3)
+        for i in 0..4u8 {
+            for _ in 0..i {
+                board.drop_piece(i, Player::Yellow, &geo);
+            }
+            board.drop_piece(i, Player::Red, &geo);
+        }
+        assert!(board.check_win(Player::Red, &geo));
+
+        let mirrored = board.mirror_horizontal();
+        assert!(mirrored.check_win(Player::Red, &geo));
+        assert!(!mirrored.check_win(Player::Yellow, &geo));
+        assert_eq!(mirrored.mirror_horizontal(), board);
+    }
+
+    #[test]
+    fn test_height_profile_matches_column_height() {
+        use rand::{RngExt, SeedableRng};
+
+        let geo = BoardGeometry::<2>::new(9, 9);
+        let mut board = Board::<2>::new(9, 9);
+        let mut rng = rand::rngs::SmallRng::seed_from_u64(79);
+
+        for _ in 0..200 {
+            let col = rng.random_range(0..9);
+            let player = if rng.random_bool(0.5) {
+                Player::Red
+            } else {
+                Player::Yellow
+            };
+            board.drop_piece(col, player, &geo);
+
+            let profile = board.height_profile(&geo);
+            for c in 0..9u8 {
+                assert_eq!(profile[c as usize], board.column_height(c, &geo));
+            }
+            assert!(profile[9..].iter().all(|&h| h == 0));
+        }
+    }
+
+    #[test]
+    fn test_place_column_stack() {
+        let geo = make_geo();
+        let mut board = Board::<{ nw_for_board(STANDARD_COLS, STANDARD_ROWS) }>::default();
+        let (r, y) = (Player::Red, Player::Yellow);
+
+        board
+            .place_column_stack(2, &[r, r, y], &geo)
+            .expect("test_place_column_stack: column has room");
+        board
+            .place_column_stack(2, &[y, r], &geo)
+            .expect("test_place_column_stack: column has room");
+        let expected = [Some(r), Some(r), Some(y), Some(y), Some(r), None];
+        for (row, &cell) in expected.iter().enumerate() {
+            assert_eq!(board.get_piece(&Position::new(2, row as u8)), cell);
+        }
+        assert_eq!(board.column_height(2, &geo), 5);
+
+        let before = board;
+        assert_eq!(
+            board.place_column_stack(2, &[y, y], &geo),
+            Err(StackError::Overflow {
+                col: 2,
+                free: 1,
+                requested: 2
+            })
+        );
+        assert_eq!(
+            board.place_column_stack(STANDARD_COLS, &[r], &geo),
+            Err(StackError::ColumnOutOfRange(STANDARD_COLS))
+        );
+        assert_eq!(board, before);
+
+        board
+            .place_column_stack(2, &[y], &geo)
+            .expect("test_place_column_stack: exactly fills the column");
+        assert!(board.is_column_full(2));
+        assert_eq!(board.place_column_stack(2, &[], &geo), Ok(()));
+    }
+
+    #[test]
+    fn test_heights_consistent() {
+        use rand::{RngExt, SeedableRng};
+
+        let geo = make_geo();
+        let mut board = Board::<{ nw_for_board(STANDARD_COLS, STANDARD_ROWS) }>::default();
+        let mut rng = rand::rngs::SmallRng::seed_from_u64(17);
+
+        for step in 0..5_000 {
+            let col = rng.random_range(0..STANDARD_COLS);
+            let player = if rng.random_bool(0.5) {
+                Player::Red
+            } else {
+                Player::Yellow
+            };
+
+            match rng.random_range(0..10) {
+                0..=5 => {
+                    board.drop_piece(col, player, &geo);
+                }
+                6..=8 => {
+                    let row = rng.random_range(0..STANDARD_ROWS);
+                    let piece = rng.random_bool(0.5).then_some(player);
+                    board.set_piece(&Position::new(col, row), piece);
+                }
+                _ => {
+                    if step % 7 == 0 {
+                        board.clear();
+                    }
+                }
+            }
+
+            for c in 0..STANDARD_COLS {
+                let expected = (board.occupied() & geo.column_masks[c as usize]).count() as u8;
+                assert_eq!(board.column_height(c, &geo), expected);
+                assert_eq!(board.is_column_full(c), expected == STANDARD_ROWS);
+            }
+        }
+    }
+
+    #[test]
+    fn test_iter_pieces_matches_scan() {
+        use rand::{RngExt, SeedableRng};
+
+        let mut rng = rand::rngs::SmallRng::seed_from_u64(69);
+        let mut board = Board::<{ nw_for_board(9, 9) }>::new(9, 9);
+        assert_eq!(board.iter_pieces().count(), 0);
+
+        for _ in 0..30 {
+            let pos = Position::new(rng.random_range(0..9), rng.random_range(0..9));
+            let player = if rng.random_bool(0.5) {
+                Player::Red
+            } else {
+                Player::Yellow
+            };
+            board.set_piece(&pos, Some(player));
+        }
+
+        let mut scanned = Vec::new();
+        for player in [Player::Red, Player::Yellow] {
+            for row in 0..9 {
+                for col in 0..9 {
+                    let pos = Position::new(col, row);
+                    if board.get_piece(&pos) == Some(player) {
+                        scanned.push((pos, player));
+                    }
+                }
+            }
+        }
+        assert_eq!(board.iter_pieces().collect::<Vec<_>>(), scanned);
+        assert_eq!(board.iter_pieces().count() as u32, board.total_count());
+    }
+
+    #[test]
+    fn test_diff() {
+        let geo = make_geo();
+        let mut board = Board::<{ nw_for_board(STANDARD_COLS, STANDARD_ROWS) }>::default();
+        board.drop_piece(3, Player::Red, &geo);
+        board.drop_piece(3, Player::Yellow, &geo);
+        assert!(board.diff(&board).is_empty());
+
+        let before = board;
+        board.drop_piece(3, Player::Red, &geo);
+        assert_eq!(
+            before.diff(&board),
+            vec![(Position::new(3, 2), None, Some(Player::Red))]
+        );
+        assert_eq!(
+            board.diff(&before),
+            vec![(Position::new(3, 2), Some(Player::Red), None)]
+        );
+
+        let swapped = board.swap_colors();
+        assert_eq!(
+            board.diff(&swapped),
+            vec![
+                (Position::new(3, 0), Some(Player::Red), Some(Player::Yellow)),
+                (Position::new(3, 1), Some(Player::Yellow), Some(Player::Red)),
+                (Position::new(3, 2), Some(Player::Red), Some(Player::Yellow)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_bytes_round_trip() {
+        use rand::{RngExt, SeedableRng};
+
+        fn check<const NW: usize>(width: u8, height: u8, seed: u64) {
+            let mut rng = rand::rngs::SmallRng::seed_from_u64(seed);
+            let expected_len = (width as usize * height as usize * 2).div_ceil(8);
+            for _ in 0..50 {
+                let mut board = Board::<NW>::new(width, height);
+                for row in 0..height {
+                    for col in 0..width {
+                        let piece = match rng.random_range(0..3) {
+                            0 => None,
+                            1 => Some(Player::Red),
+                            _ => Some(Player::Yellow),
+                        };
+                        board.set_piece(&Position::new(col, row), piece);
+                    }
+                }
+
+                let bytes = board.to_bytes();
+                assert_eq!(bytes.len(), expected_len);
+                assert_eq!(Board::<NW>::from_bytes(&bytes, width, height), Ok(board));
+            }
+        }
+
+        check::<{ nw_for_board(7, 6) }>(7, 6, 1);
+        check::<{ nw_for_board(9, 9) }>(9, 9, 2);
+    }
+
+    #[test]
+    fn test_bytes_layout() {
+        let mut board = Board::<{ nw_for_board(7, 6) }>::new(7, 6);
+        board.set_piece(&Position::new(0, 0), Some(Player::Red));
+        board.set_piece(&Position::new(1, 0), Some(Player::Yellow));
+        board.set_piece(&Position::new(5, 0), Some(Player::Red));
+        let bytes = board.to_bytes();
+        assert_eq!(bytes.len(), 11);
+        assert_eq!(bytes[0], 0b0000_1001);
+        assert_eq!(bytes[1], 0b0000_0100);
+        assert!(bytes[2..].iter().all(|&b| b == 0));
+    }
+
+    #[test]
+    fn test_from_bytes_rejects_invalid_input() {
+        type StdBoard = Board<{ nw_for_board(7, 6) }>;
+
+        assert_eq!(
+            StdBoard::from_bytes(&[0; 10], 7, 6),
+            Err(BytesError::WrongLength {
+                expected: 11,
+                found: 10
+            })
+        );
+        assert_eq!(
+            StdBoard::from_bytes(&[0; 12], 7, 6),
+            Err(BytesError::WrongLength {
+                expected: 11,
+                found: 12
+            })
+        );
+        assert_eq!(
+            StdBoard::from_bytes(&[0; 11], 9, 9),
+            Err(BytesError::InvalidDimensions {
+                width: 9,
+                height: 9
+            })
+        );
+
+        let mut bytes = vec![0; 11];
+        bytes[1] = 0b0011_0000;
+        assert_eq!(
+            StdBoard::from_bytes(&bytes, 7, 6),
+            Err(BytesError::InvalidCell(6))
+        );
+
+        // 42 cells fill 10.5 bytes; the top nibble of the last byte is padding.
+        let mut bytes = vec![0; 11];
+        bytes[10] = 0b0100_0000;
+        assert_eq!(
+            StdBoard::from_bytes(&bytes, 7, 6),
+            Err(BytesError::NonZeroPadding)
+        );
+        bytes[10] = 0b0000_0100;
+        let board = StdBoard::from_bytes(&bytes, 7, 6)
+            .expect("test_from_bytes_rejects_invalid_input: last cell is not padding");
+        assert_eq!(board.to_bytes(), bytes);
     }
 }
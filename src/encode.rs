@@ -1,6 +1,9 @@
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::board::Board;
 use crate::game::Game;
 use crate::player::Player;
-use crate::position::Position;
 use crate::r#move::Move;
 
 /// Number of planes for piece positions (1 for RED + 1 for YELLOW)
@@ -15,9 +18,19 @@ const CONSTANT_PLANES: usize = 1;
 /// Total number of input planes for the neural network
 pub const TOTAL_INPUT_PLANES: usize = (HISTORY_LENGTH * PIECE_PLANES) + CONSTANT_PLANES;
 
+/// Number of planes produced by `encode_board_planes` (own, opponent, color)
+pub const BOARD_INPUT_PLANES: usize = PIECE_PLANES + CONSTANT_PLANES;
+
+/// Index of the legal-moves plane appended by `encode_game_planes_with` when
+/// `EncodeOptions::legal_moves_plane` is set: right after the color plane.
+pub const LEGAL_MOVES_PLANE: usize = TOTAL_INPUT_PLANES;
+
 /// Encoding value indicating a piece is present in a cell
 const PIECE_PRESENT: f32 = 1.0;
 
+/// Encoding value marking the landing cell of a legal move
+const LEGAL_MOVE_PRESENT: f32 = 1.0;
+
 /// Encoding value for the color plane when the current player is Red
 const COLOR_RED: f32 = 1.0;
 
@@ -26,76 +39,236 @@ const COLOR_YELLOW: f32 = 0.0;
 
 /// Encode the full game state into a flat f32 array for efficient transfer to Python/numpy
 /// Returns (flat_data, num_planes, height, width), where flat_data is in row-major order
+///
+/// The game is not modified; the `&mut` receiver is kept for compatibility.
+/// Prefer `encode_game_planes_ref`.
 pub fn encode_game_planes<const NW: usize>(game: &mut Game<NW>) -> (Vec<f32>, usize, usize, usize) {
-    let perspective = game.turn();
+    encode_game_planes_ref(game)
+}
+
+/// Same as `encode_game_planes`, but takes the game by shared reference.
+///
+/// Historical boards are rebuilt on a copy of the current board by removing
+/// the stones of the most recent moves, so stones placed with `set_piece`
+/// outside the move history are preserved exactly as `unmake_move` would.
+pub fn encode_game_planes_ref<const NW: usize>(game: &Game<NW>) -> (Vec<f32>, usize, usize, usize) {
     let width = game.width() as usize;
     let height = game.height() as usize;
     let num_planes = TOTAL_INPUT_PLANES;
+    let mut data = vec![0.0f32; num_planes * height * width];
+
+    write_game_planes(game, &mut data);
+
+    (data, num_planes, height, width)
+}
+
+/// Axis order of encoded planes.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Layout {
+    /// `(planes, height, width)`, as produced by `encode_game_planes`.
+    #[default]
+    ChannelsFirst,
+    /// `(height, width, planes)`: all plane values for a cell are adjacent.
+    ChannelsLast,
+}
+
+/// Same as `encode_game_planes_ref`, with the flat data in the given axis
+/// order. The three returned dimensions follow that order too: `(num_planes,
+/// height, width)` for `ChannelsFirst`, `(height, width, num_planes)` for
+/// `ChannelsLast`.
+pub fn encode_game_planes_layout<const NW: usize>(
+    game: &Game<NW>,
+    layout: Layout,
+) -> (Vec<f32>, usize, usize, usize) {
+    encode_game_planes_with(
+        game,
+        &EncodeOptions {
+            layout,
+            ..EncodeOptions::default()
+        },
+    )
+}
+
+/// Options for `encode_game_planes_with`. The default matches
+/// `encode_game_planes`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct EncodeOptions {
+    /// Axis order of the returned data and dimensions.
+    pub layout: Layout,
+    /// Append one plane, at index `LEGAL_MOVES_PLANE`, that is 1 at the cell
+    /// each legal move lands on and 0 elsewhere (all 0 once the game is over).
+    pub legal_moves_plane: bool,
+}
+
+/// Encode the game planes with the given options. Returns the flat data and
+/// its three dimensions in the order set by `options.layout`, as for
+/// `encode_game_planes_layout`; `num_planes` is `TOTAL_INPUT_PLANES`, plus one
+/// with the legal-moves plane.
+pub fn encode_game_planes_with<const NW: usize>(
+    game: &Game<NW>,
+    options: &EncodeOptions,
+) -> (Vec<f32>, usize, usize, usize) {
+    let (mut data, mut num_planes, height, width) = encode_game_planes_ref(game);
+
+    if options.legal_moves_plane {
+        let board_size = height * width;
+        let mut plane = vec![0.0f32; board_size];
+        for move_ in game.legal_moves_iter() {
+            plane[move_.row as usize * width + move_.col as usize] = LEGAL_MOVE_PRESENT;
+        }
+        data.extend_from_slice(&plane);
+        num_planes += 1;
+    }
+
+    match options.layout {
+        Layout::ChannelsFirst => (data, num_planes, height, width),
+        Layout::ChannelsLast => {
+            let board_size = height * width;
+            let mut transposed = vec![0.0f32; data.len()];
+            for (plane, values) in data.chunks_exact(board_size).enumerate() {
+                for (cell, &value) in values.iter().enumerate() {
+                    transposed[cell * num_planes + plane] = value;
+                }
+            }
+            (transposed, height, width, num_planes)
+        }
+    }
+}
+
+/// Encode many games into a caller-provided buffer, one contiguous block of
+/// `TOTAL_INPUT_PLANES * height * width` values per game, in the same layout as
+/// `encode_game_planes`. All games must share the same dimensions, and
+/// `out.len()` must equal `games.len() * TOTAL_INPUT_PLANES * height * width`.
+pub fn encode_games_into<const NW: usize>(games: &[Game<NW>], out: &mut [f32]) {
+    let Some(first) = games.first() else {
+        assert!(
+            out.is_empty(),
+            "encode_games_into: no games but non-empty output"
+        );
+        return;
+    };
+    let width = first.width();
+    let height = first.height();
+    let block_size = TOTAL_INPUT_PLANES * height as usize * width as usize;
+
+    assert!(
+        games
+            .iter()
+            .all(|g| g.width() == width && g.height() == height),
+        "encode_games_into: all games must be {}x{}",
+        width,
+        height
+    );
+    assert_eq!(
+        out.len(),
+        games.len() * block_size,
+        "encode_games_into: output buffer has wrong length"
+    );
+
+    for (game, block) in games.iter().zip(out.chunks_exact_mut(block_size)) {
+        write_game_planes(game, block);
+    }
+}
+
+/// Write the planes for one game into `data`, which must be exactly
+/// `TOTAL_INPUT_PLANES * height * width` long. Any previous contents are overwritten.
+fn write_game_planes<const NW: usize>(game: &Game<NW>, data: &mut [f32]) {
+    let perspective = game.turn();
+    let width = game.width() as usize;
+    let height = game.height() as usize;
     let board_size = height * width;
-    let total_size = num_planes * board_size;
-    let mut data = vec![0.0f32; total_size];
+    debug_assert_eq!(data.len(), TOTAL_INPUT_PLANES * board_size);
+    data.fill(0.0);
 
     let history = game.move_history();
     let history_len = history.len();
     let steps_back = (HISTORY_LENGTH - 1).min(history_len);
 
-    let moves_to_replay: Vec<Move> = history[(history_len - steps_back)..].to_vec();
+    let mut board = *game.board();
 
     // T=0: current position
-    fill_connect4_planes(&mut data, game, perspective, 0, width, height);
+    fill_connect4_planes(data, &board, perspective, 0);
 
-    // T=1..steps_back: walk backward through history
+    // T=1..steps_back: walk backward through history on the copied board
     for t in 1..=steps_back {
-        game.unmake_move();
-        fill_connect4_planes(&mut data, game, perspective, t, width, height);
-    }
-
-    // Replay saved moves to restore game state
-    for mv in &moves_to_replay {
-        game.make_move(mv);
+        let undone = history[history_len - t];
+        if !undone.is_null() {
+            board.set_piece(&undone.position(), None);
+        }
+        fill_connect4_planes(data, &board, perspective, t);
     }
 
     // Color plane (last plane)
-    let color_plane = HISTORY_LENGTH * PIECE_PLANES;
-    let color_value = if perspective == Player::Red {
+    let color_offset = HISTORY_LENGTH * PIECE_PLANES * board_size;
+    data[color_offset..color_offset + board_size].fill(color_value(perspective));
+}
+
+/// Encode only the current position as `[own, opponent, color]` planes, in the
+/// same layout and values as the T=0 and color planes of `encode_game_planes`.
+/// Returns (flat_data, num_planes, height, width). No history is walked.
+pub fn encode_board_planes<const NW: usize>(game: &Game<NW>) -> (Vec<f32>, usize, usize, usize) {
+    let perspective = game.turn();
+    let width = game.width() as usize;
+    let height = game.height() as usize;
+    let board_size = height * width;
+    let mut data = vec![0.0; BOARD_INPUT_PLANES * board_size];
+
+    fill_connect4_planes(&mut data, game.board(), perspective, 0);
+
+    let color_offset = PIECE_PLANES * board_size;
+    data[color_offset..].fill(color_value(perspective));
+
+    (data, BOARD_INPUT_PLANES, height, width)
+}
+
+fn color_value(perspective: Player) -> f32 {
+    if perspective == Player::Red {
         COLOR_RED
     } else {
         COLOR_YELLOW
-    };
-    let color_offset = color_plane * board_size;
-    for i in 0..board_size {
-        data[color_offset + i] = color_value;
     }
+}
 
-    (data, num_planes, height, width)
+/// Mark `board`'s stones in two planes of `out`: cells holding a
+/// `perspective` stone are set to 1 in plane `own_plane`, cells holding an
+/// opponent stone in plane `opp_plane`. Each plane is `height * width` values
+/// in row-major order from the bottom row, plane `p` starting at
+/// `p * height * width`. Other values are left untouched, so `out` should be
+/// zeroed first. This is the building block of `encode_game_planes`, exposed
+/// for composing custom encodings.
+pub fn fill_planes_for<const NW: usize>(
+    board: &Board<NW>,
+    perspective: Player,
+    own_plane: usize,
+    opp_plane: usize,
+    out: &mut [f32],
+) {
+    let board_size = board.width() as usize * board.height() as usize;
+    let own_offset = own_plane * board_size;
+    let opp_offset = opp_plane * board_size;
+
+    for idx in board.stones_for(perspective).iter_ones() {
+        out[own_offset + idx] = PIECE_PRESENT;
+    }
+    for idx in board.stones_for(perspective.opposite()).iter_ones() {
+        out[opp_offset + idx] = PIECE_PRESENT;
+    }
 }
 
+/// Fill the own/opponent planes for history step `t`.
 fn fill_connect4_planes<const NW: usize>(
     data: &mut [f32],
-    game: &Game<NW>,
+    board: &Board<NW>,
     perspective: Player,
     t: usize,
-    width: usize,
-    height: usize,
 ) {
-    let opponent = perspective.opposite();
-    let board_size = height * width;
-    let own_offset = t * PIECE_PLANES * board_size;
-    let opp_offset = (t * PIECE_PLANES + 1) * board_size;
-
-    for row in 0..height {
-        for col in 0..width {
-            let pos = Position::new(col as u8, row as u8);
-            if let Some(player) = game.board().get_piece(&pos) {
-                let idx = row * width + col;
-                if player == perspective {
-                    data[own_offset + idx] = PIECE_PRESENT;
-                } else if player == opponent {
-                    data[opp_offset + idx] = PIECE_PRESENT;
-                }
-            }
-        }
-    }
+    fill_planes_for(
+        board,
+        perspective,
+        t * PIECE_PLANES,
+        t * PIECE_PLANES + 1,
+        data,
+    );
 }
 
 /// Encode a move as an action index for the policy head
@@ -103,6 +276,49 @@ pub fn encode_move(move_: &Move) -> usize {
     move_.col as usize
 }
 
+/// Encode a move as the action index it maps to on the horizontally mirrored
+/// board, for reflecting policy targets alongside `Board::mirror_horizontal`
+pub fn encode_move_mirrored(move_: &Move, width: u8) -> usize {
+    encode_move(&move_.mirror_horizontal(width))
+}
+
+/// Normalize per-column visit counts into a policy target over the action
+/// layout of `encode_move`, i.e. one probability per column.
+/// `counts` must have exactly `width` entries. If all counts are zero, the
+/// result is all zeros.
+pub fn encode_policy(counts: &[f32], width: usize) -> Vec<f32> {
+    assert_eq!(
+        counts.len(),
+        width,
+        "Expected {} visit counts, got {}",
+        width,
+        counts.len()
+    );
+    let total: f32 = counts.iter().sum();
+    if total <= 0.0 {
+        return vec![0.0; width];
+    }
+    counts.iter().map(|&c| c / total).collect()
+}
+
+/// Same as `encode_policy`, but columns where `legal_mask` is false get zero
+/// probability and the rest are renormalized. `legal_mask` needs at least
+/// `width` entries, so `Game::legal_action_mask` can be passed directly.
+pub fn encode_policy_masked(counts: &[f32], width: usize, legal_mask: &[bool]) -> Vec<f32> {
+    assert!(
+        legal_mask.len() >= width,
+        "Legal mask has {} entries, expected at least {}",
+        legal_mask.len(),
+        width
+    );
+    let masked: Vec<f32> = counts
+        .iter()
+        .zip(legal_mask)
+        .map(|(&c, &legal)| if legal { c } else { 0.0 })
+        .collect();
+    encode_policy(&masked, width)
+}
+
 /// Decode an action index back to a move
 /// Returns the column number and row where the piece would land
 pub fn decode_move<const NW: usize>(action: usize, game: &Game<NW>) -> Option<Move> {
@@ -125,6 +341,7 @@ mod tests {
     use super::*;
     use crate::bitboard::nw_for_board;
     use crate::board::{STANDARD_COLS, STANDARD_ROWS};
+    use crate::position::Position;
 
     type StdGame = Game<{ nw_for_board(STANDARD_COLS, STANDARD_ROWS) }>;
 
@@ -162,6 +379,165 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_encode_games_into_matches_individual() {
+        let mut games: Vec<StdGame> = (0..5).map(|_| standard_game()).collect();
+        for (i, game) in games.iter_mut().enumerate() {
+            for j in 0..(i * 3) {
+                let legal_moves = game.legal_moves();
+                game.make_move(&legal_moves[(i + j) % legal_moves.len()]);
+            }
+        }
+
+        let block_size = TOTAL_INPUT_PLANES * STANDARD_ROWS as usize * STANDARD_COLS as usize;
+        // Pre-fill with garbage to check the buffer is fully overwritten
+        let mut out = vec![7.0f32; games.len() * block_size];
+        encode_games_into(&games, &mut out);
+
+        for (i, game) in games.iter_mut().enumerate() {
+            let history_before = game.move_history().to_vec();
+            let (expected, _, _, _) = encode_game_planes(game);
+            assert_eq!(
+                &out[i * block_size..(i + 1) * block_size],
+                expected.as_slice()
+            );
+            assert_eq!(game.move_history(), history_before.as_slice());
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "wrong length")]
+    fn test_encode_games_into_wrong_length() {
+        let games = vec![standard_game(), standard_game()];
+        let mut out = vec![0.0f32; 10];
+        encode_games_into(&games, &mut out);
+    }
+
+    /// The original encoder: walks back through history with `unmake_move` and
+    /// replays the moves afterwards. Kept as a reference implementation.
+    fn encode_by_unmaking(game: &mut StdGame) -> Vec<f32> {
+        let perspective = game.turn();
+        let width = game.width() as usize;
+        let height = game.height() as usize;
+        let board_size = height * width;
+        let mut data = vec![0.0f32; TOTAL_INPUT_PLANES * board_size];
+
+        let history = game.move_history();
+        let steps_back = (HISTORY_LENGTH - 1).min(history.len());
+        let moves_to_replay: Vec<Move> = history[(history.len() - steps_back)..].to_vec();
+
+        fill_connect4_planes(&mut data, game.board(), perspective, 0);
+        for t in 1..=steps_back {
+            game.unmake_move();
+            fill_connect4_planes(&mut data, game.board(), perspective, t);
+        }
+        for mv in &moves_to_replay {
+            game.make_move(mv);
+        }
+
+        let color_offset = HISTORY_LENGTH * PIECE_PLANES * board_size;
+        let color_value = if perspective == Player::Red {
+            COLOR_RED
+        } else {
+            COLOR_YELLOW
+        };
+        data[color_offset..].fill(color_value);
+        data
+    }
+
+    #[test]
+    fn test_encode_game_planes_ref_matches_unmaking() {
+        use rand::prelude::IndexedRandom;
+        use rand::SeedableRng;
+
+        let mut rng = rand::rngs::SmallRng::seed_from_u64(7);
+
+        for _ in 0..50 {
+            let mut game = standard_game();
+            while !game.is_over() {
+                let history_before = game.move_history().to_vec();
+                let board_before = *game.board();
+
+                let (data, num_planes, height, width) = encode_game_planes_ref(&game);
+                assert_eq!(game.move_history(), history_before.as_slice());
+                assert_eq!(game.board(), &board_before);
+
+                assert_eq!(num_planes, TOTAL_INPUT_PLANES);
+                assert_eq!((height, width), (6, 7));
+                let expected = encode_by_unmaking(&mut game);
+                assert_eq!(
+                    data.iter().map(|v| v.to_bits()).collect::<Vec<_>>(),
+                    expected.iter().map(|v| v.to_bits()).collect::<Vec<_>>()
+                );
+
+                let legal_moves = game.legal_moves();
+                let chosen_move = legal_moves
+                    .choose(&mut rng)
+                    .expect("test_encode_game_planes_ref_matches_unmaking: no legal moves");
+                game.make_move(chosen_move);
+            }
+        }
+    }
+
+    #[test]
+    fn test_encode_game_planes_after_resignation() {
+        let mut game = standard_game();
+        game.make_move(&Move::new(0, 0));
+        game.make_move(&Move::new(1, 0));
+        let (before, _, _, _) = encode_game_planes_ref(&game);
+
+        game.resign(Player::Red);
+        let (after, _, height, width) = encode_game_planes(&mut game);
+        assert_eq!(after, before);
+
+        // T=1 (before Yellow's move) has Red at (0,0) only
+        assert_eq!(get_plane_value(&after, 2, 0, 0, height, width), 1.0);
+        assert_eq!(get_plane_value(&after, 3, 0, 1, height, width), 0.0);
+    }
+
+    #[test]
+    fn test_encode_game_planes_with_null_move() {
+        let mut game = standard_game();
+        game.make_move(&Move::new(0, 0));
+        game.make_null_move();
+        game.make_move(&Move::new(1, 0));
+        let (data, _, height, width) = encode_game_planes_ref(&game);
+
+        // Yellow to move, having passed. T=0 and T=1 differ by Red's second stone;
+        // T=1 and T=2 are the same board, as the null move changed nothing.
+        assert_eq!(get_plane_value(&data, 1, 0, 1, height, width), 1.0);
+        assert_eq!(get_plane_value(&data, 3, 0, 1, height, width), 0.0);
+        let board_size = height * width;
+        assert_eq!(
+            data[2 * board_size..4 * board_size],
+            data[4 * board_size..6 * board_size]
+        );
+        assert_eq!(get_plane_value(&data, 5, 0, 0, height, width), 1.0);
+        assert_eq!(get_plane_value(&data, 7, 0, 0, height, width), 0.0);
+    }
+
+    #[test]
+    fn test_encode_invariant_under_swap_players() {
+        let mut game = standard_game();
+        for col in [3, 3, 2, 4, 4, 5, 1, 0, 6, 6] {
+            let row = game.board().column_height(col, game.geo());
+            game.make_move(&Move::new(col, row));
+        }
+        let swapped = game.swap_players();
+
+        let (data, _, height, width) = encode_game_planes_ref(&game);
+        let (swapped_data, _, _, _) = encode_game_planes_ref(&swapped);
+
+        // Piece planes are relative to the side to move, so they are unchanged;
+        // only the color plane differs.
+        let color_offset = HISTORY_LENGTH * PIECE_PLANES * height * width;
+        assert_eq!(data[..color_offset], swapped_data[..color_offset]);
+        assert!(data[color_offset..].iter().all(|&v| v == COLOR_RED));
+        assert!(swapped_data[color_offset..]
+            .iter()
+            .all(|&v| v == COLOR_YELLOW));
+    }
+
     #[test]
     fn test_encode_decode_move() {
         let game = standard_game();
@@ -178,6 +554,23 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_encode_move_mirrored() {
+        let game = standard_game();
+
+        for col in 0..game.width() {
+            let move_ = Move::new(col, 0);
+            let mirrored = encode_move_mirrored(&move_, game.width());
+            assert_eq!(mirrored, (game.width() - 1 - col) as usize);
+            assert_eq!(
+                move_
+                    .mirror_horizontal(game.width())
+                    .mirror_horizontal(game.width()),
+                move_
+            );
+        }
+    }
+
     #[test]
     fn test_encode_game_with_pieces() {
         let mut game = standard_game();
@@ -465,4 +858,176 @@ mod tests {
 
         assert_ne!(data1.len(), data2.len());
     }
+
+    #[test]
+    fn test_encode_policy_normalizes() {
+        let policy = encode_policy(
+            &[0.0, 2.0, 4.0, 10.0, 4.0, 0.0, 0.0],
+            STANDARD_COLS as usize,
+        );
+        assert_eq!(policy.len(), STANDARD_COLS as usize);
+        assert_eq!(policy, vec![0.0, 0.1, 0.2, 0.5, 0.2, 0.0, 0.0]);
+        assert!((policy.iter().sum::<f32>() - 1.0).abs() < 1e-6);
+
+        assert_eq!(encode_policy(&[0.0; 7], 7), vec![0.0; 7]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Expected 7 visit counts")]
+    fn test_encode_policy_wrong_length() {
+        encode_policy(&[1.0, 2.0], 7);
+    }
+
+    #[test]
+    fn test_encode_policy_masked() {
+        let mut game = standard_game();
+        for _ in 0..STANDARD_ROWS {
+            let move_ = decode_move(0, &game).expect("test_encode_policy_masked: column 0 open");
+            game.make_move(&move_);
+        }
+
+        let counts = [6.0, 1.0, 1.0, 2.0, 0.0, 0.0, 0.0];
+        let policy = encode_policy_masked(&counts, 7, &game.legal_action_mask());
+        assert_eq!(policy, vec![0.0, 0.25, 0.25, 0.5, 0.0, 0.0, 0.0]);
+
+        let all_legal = [true; 7];
+        assert_eq!(
+            encode_policy_masked(&counts, 7, &all_legal),
+            encode_policy(&counts, 7)
+        );
+        assert_eq!(encode_policy_masked(&counts, 7, &[false; 7]), vec![0.0; 7]);
+    }
+
+    #[test]
+    fn test_encode_board_planes_matches_full_encoding() {
+        let mut game = standard_game();
+        for col in [3, 3, 2, 4, 4, 0, 6] {
+            let (data, num_planes, height, width) = encode_board_planes(&game);
+            assert_eq!(num_planes, 3);
+            assert_eq!((height, width), (6, 7));
+
+            let board_size = height * width;
+            let (full, _, _, _) = encode_game_planes_ref(&game);
+            let color_offset = (TOTAL_INPUT_PLANES - 1) * board_size;
+            assert_eq!(&data[..2 * board_size], &full[..2 * board_size]);
+            assert_eq!(&data[2 * board_size..], &full[color_offset..]);
+
+            let move_ = decode_move(col, &game)
+                .expect("test_encode_board_planes_matches_full_encoding: column open");
+            game.make_move(&move_);
+        }
+    }
+
+    #[test]
+    fn test_encode_game_planes_layout() {
+        let mut game = standard_game();
+        for col in [3, 3, 2, 4] {
+            let move_ =
+                decode_move(col, &game).expect("test_encode_game_planes_layout: column open");
+            game.make_move(&move_);
+        }
+
+        let first = encode_game_planes_layout(&game, Layout::default());
+        assert_eq!(first, encode_game_planes_ref(&game));
+        let (first, planes, height, width) = first;
+
+        let (last, h, w, p) = encode_game_planes_layout(&game, Layout::ChannelsLast);
+        assert_eq!((h, w, p), (height, width, planes));
+        assert_eq!(last.len(), first.len());
+        for plane in 0..planes {
+            for row in 0..height {
+                for col in 0..width {
+                    assert_eq!(
+                        last[(row * width + col) * planes + plane],
+                        first[(plane * height + row) * width + col]
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_encode_game_planes_with_legal_moves_plane() {
+        let mut game = standard_game();
+        for col in [3, 3, 2, 4, 0, 0, 0, 0, 0, 0] {
+            let move_ = decode_move(col, &game)
+                .expect("test_encode_game_planes_with_legal_moves_plane: column open");
+            game.make_move(&move_);
+        }
+        assert!(game.board().is_column_full(0));
+
+        let options = EncodeOptions {
+            legal_moves_plane: true,
+            ..EncodeOptions::default()
+        };
+        let (data, planes, height, width) = encode_game_planes_with(&game, &options);
+        assert_eq!(planes, TOTAL_INPUT_PLANES + 1);
+        assert_eq!(data.len(), planes * height * width);
+
+        let board_size = height * width;
+        let (base, _, _, _) = encode_game_planes_ref(&game);
+        assert_eq!(&data[..LEGAL_MOVES_PLANE * board_size], base.as_slice());
+
+        let legal_plane = &data[LEGAL_MOVES_PLANE * board_size..];
+        let landing: Vec<usize> = game
+            .legal_moves()
+            .iter()
+            .map(|m| m.row as usize * width + m.col as usize)
+            .collect();
+        assert_eq!(landing.len(), 6);
+        for (cell, &value) in legal_plane.iter().enumerate() {
+            let expected = if landing.contains(&cell) { 1.0 } else { 0.0 };
+            assert_eq!(value, expected, "cell {}", cell);
+        }
+
+        let last = EncodeOptions {
+            layout: Layout::ChannelsLast,
+            legal_moves_plane: true,
+        };
+        let (data_last, h, w, p) = encode_game_planes_with(&game, &last);
+        assert_eq!((h, w, p), (height, width, planes));
+        for &cell in &landing {
+            assert_eq!(data_last[cell * planes + LEGAL_MOVES_PLANE], 1.0);
+        }
+
+        let (default_data, ..) = encode_game_planes_with(&game, &EncodeOptions::default());
+        assert_eq!(default_data, base);
+    }
+
+    #[test]
+    fn test_fill_planes_for_matches_cell_scan() {
+        let mut game = Game::<{ nw_for_board(9, 9) }>::new(9, 9);
+        for col in [4, 4, 3, 8, 4, 0, 8, 8, 1] {
+            let move_ =
+                decode_move(col, &game).expect("test_fill_planes_for_matches_cell_scan: open");
+            game.make_move(&move_);
+        }
+        let board = game.board();
+        let (width, height) = (9, 9);
+        let board_size = width * height;
+
+        for perspective in [Player::Red, Player::Yellow] {
+            // Planes 3 and 1 of 4, to check the offsets are honoured.
+            let mut out = vec![0.0f32; 4 * board_size];
+            fill_planes_for(board, perspective, 3, 1, &mut out);
+
+            let mut expected = vec![0.0f32; 4 * board_size];
+            for row in 0..height {
+                for col in 0..width {
+                    let idx = row * width + col;
+                    match board.get_piece(&Position::new(col as u8, row as u8)) {
+                        Some(p) if p == perspective => expected[3 * board_size + idx] = 1.0,
+                        Some(_) => expected[board_size + idx] = 1.0,
+                        None => {}
+                    }
+                }
+            }
+            assert_eq!(out, expected);
+        }
+
+        let (data, ..) = encode_game_planes_ref(&game);
+        let mut t0 = vec![0.0f32; 2 * board_size];
+        fill_planes_for(board, game.turn(), 0, 1, &mut t0);
+        assert_eq!(&data[..2 * board_size], t0.as_slice());
+    }
 }
@@ -1,6 +1,15 @@
-use crate::bitboard::BoardGeometry;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+#[cfg(feature = "serde")]
+use crate::bitboard::MAX_CONNECT_LEN;
+use crate::bitboard::{
+    nw_for_board, Bitboard, BoardGeometry, WinDirection, DEFAULT_CONNECT_LEN, MIN_CONNECT_LEN,
+};
 use crate::board::Board;
 use crate::encode::HISTORY_LENGTH;
+use crate::limits::{board_dimension_is_valid, MAX_BOARD_DIMENSION, MIN_BOARD_DIMENSION};
 use crate::outcome::GameOutcome;
 use crate::player::Player;
 use crate::position::Position;
@@ -8,6 +17,287 @@ use crate::r#move::Move;
 
 const STATE_HASH_HISTORY_LENGTH: usize = HISTORY_LENGTH - 1;
 
+/// Lazily filled legal-move list. `OnceLock` keeps `Game` `Sync` (needed by
+/// the Python bindings); without `std` the single-threaded `OnceCell` stands in.
+#[cfg(feature = "std")]
+type LegalMovesCache = std::sync::OnceLock<Vec<Move>>;
+#[cfg(not(feature = "std"))]
+type LegalMovesCache = core::cell::OnceCell<Vec<Move>>;
+
+/// Error returned by `Game::place_stones`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PlacementError {
+    /// The position lies outside the board.
+    OutOfBounds(Position),
+    /// The position was given more than once.
+    DuplicatePosition(Position),
+    /// The stone has an empty cell below it.
+    FloatingStone(Position),
+    /// Red, who moves first, does not have as many stones as Yellow or one
+    /// more, which no game can reach.
+    InvalidStoneCounts { reds: usize, yellows: usize },
+}
+
+impl core::fmt::Display for PlacementError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            PlacementError::OutOfBounds(pos) => {
+                write!(f, "Position ({}, {}) is off the board", pos.col, pos.row)
+            }
+            PlacementError::DuplicatePosition(pos) => {
+                write!(
+                    f,
+                    "Position ({}, {}) is given more than once",
+                    pos.col, pos.row
+                )
+            }
+            PlacementError::FloatingStone(pos) => write!(
+                f,
+                "Stone at ({}, {}) has an empty cell below it",
+                pos.col, pos.row
+            ),
+            PlacementError::InvalidStoneCounts { reds, yellows } => write!(
+                f,
+                "{} red and {} yellow stones cannot arise in a game",
+                reds, yellows
+            ),
+        }
+    }
+}
+
+impl core::error::Error for PlacementError {}
+
+/// Error returned by `Game::try_move`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum MoveError {
+    /// The game had already ended.
+    GameOver,
+    /// The column does not exist on this board.
+    ColumnOutOfRange(u8),
+    /// The column has no empty cell.
+    ColumnFull(u8),
+    /// The row is not where a stone dropped into the column would land.
+    WrongRow { col: u8, row: u8, landing_row: u8 },
+}
+
+impl core::fmt::Display for MoveError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            MoveError::GameOver => write!(f, "The game is already over"),
+            MoveError::ColumnOutOfRange(col) => write!(f, "Column {} is off the board", col),
+            MoveError::ColumnFull(col) => write!(f, "Column {} is full", col),
+            MoveError::WrongRow {
+                col,
+                row,
+                landing_row,
+            } => write!(
+                f,
+                "A stone in column {} lands on row {}, not row {}",
+                col, landing_row, row
+            ),
+        }
+    }
+}
+
+impl core::error::Error for MoveError {}
+
+/// What would happen if a move were played, as reported by `Game::probe_move`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MoveResult {
+    /// Row the stone lands on.
+    pub landing_row: u8,
+    /// The move completes a line for the side to move.
+    pub wins: bool,
+    /// The move fills the board without winning.
+    pub draws: bool,
+}
+
+/// Baseline evaluation features for a position, as returned by
+/// `Game::eval_features`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct EvalFeatures {
+    /// Red's `Game::threat_cells`.
+    pub red_threats: u32,
+    /// Yellow's `Game::threat_cells`.
+    pub yellow_threats: u32,
+    pub red_stones: u32,
+    pub yellow_stones: u32,
+    /// Red stones minus Yellow stones in `BoardGeometry::center_mask`.
+    pub center_control: i32,
+}
+
+/// Error returned by `Game::from_move_string`. `ply` is the 0-based position
+/// of the offending character.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum MoveStringError {
+    /// The character is not a column digit or `-`.
+    InvalidCharacter { ply: usize, ch: char },
+    /// The column does not exist on this board.
+    ColumnOutOfRange { ply: usize, col: usize },
+    /// The column has no empty cell.
+    ColumnFull { ply: usize, col: u8 },
+    /// The game had already ended before this move.
+    GameOver { ply: usize },
+}
+
+impl core::fmt::Display for MoveStringError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            MoveStringError::InvalidCharacter { ply, ch } => {
+                write!(f, "Invalid character '{}' at ply {}", ch, ply)
+            }
+            MoveStringError::ColumnOutOfRange { ply, col } => {
+                write!(f, "Column {} at ply {} is off the board", col, ply)
+            }
+            MoveStringError::ColumnFull { ply, col } => {
+                write!(f, "Column {} at ply {} is full", col, ply)
+            }
+            MoveStringError::GameOver { ply } => {
+                write!(f, "Move at ply {} comes after the game ended", ply)
+            }
+        }
+    }
+}
+
+impl core::error::Error for MoveStringError {}
+
+/// Error returned by `GameBuilder::build`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum BuildError {
+    /// A dimension is outside `MIN_BOARD_DIMENSION..=MAX_BOARD_DIMENSION`.
+    InvalidDimensions { width: u8, height: u8 },
+    /// The board needs `needed` words per bitboard, not the `NW` requested.
+    WordCountMismatch { needed: usize, nw: usize },
+    /// No line of `connect_len` stones fits on the board.
+    InvalidConnectLen { connect_len: u8, max: u8 },
+    /// Stones always drop to the lowest empty row; free placement is not
+    /// implemented.
+    GravityRequired,
+}
+
+impl core::fmt::Display for BuildError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            BuildError::InvalidDimensions { width, height } => write!(
+                f,
+                "Board {}x{} must have sides between {} and {}",
+                width, height, MIN_BOARD_DIMENSION, MAX_BOARD_DIMENSION
+            ),
+            BuildError::WordCountMismatch { needed, nw } => {
+                write!(f, "NW={} does not match the board (need {})", nw, needed)
+            }
+            BuildError::InvalidConnectLen { connect_len, max } => write!(
+                f,
+                "Connection length {} must be between {} and {}",
+                connect_len, MIN_CONNECT_LEN, max
+            ),
+            BuildError::GravityRequired => write!(f, "Games without gravity are not supported"),
+        }
+    }
+}
+
+impl core::error::Error for BuildError {}
+
+/// Error returned by `Game::from_record`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RecordError {
+    /// The board size or connection length cannot be built.
+    Rules(BuildError),
+    /// A move cannot be replayed; `ply` indexes `GameRecord::moves`.
+    Move(MoveStringError),
+}
+
+impl core::fmt::Display for RecordError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            RecordError::Rules(err) => write!(f, "Invalid rules: {}", err),
+            RecordError::Move(err) => write!(f, "Invalid move: {}", err),
+        }
+    }
+}
+
+impl core::error::Error for RecordError {}
+
+/// Validated construction of nonstandard games. Starts from the standard
+/// 7x6 Connect Four; `Game::new` and `Game::with_connect_len` remain the
+/// shortcuts when the arguments are known to be valid. Only games with
+/// gravity are implemented, so `gravity(false)` fails to build.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct GameBuilder {
+    width: u8,
+    height: u8,
+    connect_len: u8,
+    gravity: bool,
+}
+
+impl Default for GameBuilder {
+    fn default() -> Self {
+        GameBuilder {
+            width: 7,
+            height: 6,
+            connect_len: DEFAULT_CONNECT_LEN,
+            gravity: true,
+        }
+    }
+}
+
+impl GameBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn width(mut self, width: u8) -> Self {
+        self.width = width;
+        self
+    }
+
+    pub fn height(mut self, height: u8) -> Self {
+        self.height = height;
+        self
+    }
+
+    pub fn connect_len(mut self, connect_len: u8) -> Self {
+        self.connect_len = connect_len;
+        self
+    }
+
+    /// Whether stones drop to the lowest empty row. Only `true` builds.
+    pub fn gravity(mut self, gravity: bool) -> Self {
+        self.gravity = gravity;
+        self
+    }
+
+    /// Check the rules and create the game. `connect_len` must be at least 2
+    /// and fit along the longer side of the board.
+    pub fn build<const NW: usize>(&self) -> Result<Game<NW>, BuildError> {
+        let (width, height) = (self.width, self.height);
+        if !board_dimension_is_valid(width) || !board_dimension_is_valid(height) {
+            return Err(BuildError::InvalidDimensions { width, height });
+        }
+        let needed = nw_for_board(width, height);
+        if needed != NW {
+            return Err(BuildError::WordCountMismatch { needed, nw: NW });
+        }
+        let max = width.max(height);
+        if !(MIN_CONNECT_LEN..=max).contains(&self.connect_len) {
+            return Err(BuildError::InvalidConnectLen {
+                connect_len: self.connect_len,
+                max,
+            });
+        }
+        if !self.gravity {
+            return Err(BuildError::GravityRequired);
+        }
+        Ok(Game::with_connect_len(width, height, self.connect_len))
+    }
+}
+
+/// Radix for move-string characters: `0`-`9` then `a`-`z`.
+const MOVE_STRING_RADIX: u32 = 36;
+
+/// Marker for a null move in a move string.
+const NULL_MOVE_CHAR: char = '-';
+
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct StateHash<const NW: usize> {
     board: Board<NW>,
@@ -26,6 +316,11 @@ pub struct TranspositionHash<const NW: usize> {
 }
 
 #[derive(Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(into = "GameData<NW>", try_from = "GameData<NW>")
+)]
 pub struct Game<const NW: usize> {
     board: Board<NW>,
     geo: BoardGeometry<NW>,
@@ -33,18 +328,180 @@ pub struct Game<const NW: usize> {
     move_history: Vec<Move>,
     is_over: bool,
     outcome: Option<GameOutcome>,
+    win_direction: Option<WinDirection>,
+    /// Legal moves, computed on first request and cleared by every mutation
+    /// that can change them.
+    legal_moves_cache: LegalMovesCache,
 }
 
 impl<const NW: usize> Game<NW> {
     pub fn new(width: u8, height: u8) -> Self {
+        Self::with_connect_len(width, height, DEFAULT_CONNECT_LEN)
+    }
+
+    /// Create a game where `connect_len` stones in a row win (Connect-N).
+    pub fn with_connect_len(width: u8, height: u8, connect_len: u8) -> Self {
         Game {
             board: Board::new(width, height),
-            geo: BoardGeometry::new(width, height),
+            geo: BoardGeometry::with_connect_len(width, height, connect_len),
             current_player: Player::Red,
             move_history: Vec::new(),
             is_over: false,
             outcome: None,
+            win_direction: None,
+            legal_moves_cache: LegalMovesCache::new(),
+        }
+    }
+
+    /// Return to the starting position in place, keeping the geometry (and
+    /// the history's allocation) so the game can be reused across episodes.
+    pub fn reset(&mut self) {
+        self.board = Board::new(self.board.width(), self.board.height());
+        self.current_player = Player::Red;
+        self.move_history.clear();
+        self.is_over = false;
+        self.outcome = None;
+        self.win_direction = None;
+        self.invalidate_legal_moves();
+    }
+
+    /// Replay a column-sequence string such as `"4453"`, with columns
+    /// numbered from 1. See `from_move_string_with_base`.
+    pub fn from_move_string(width: u8, height: u8, s: &str) -> Result<Self, MoveStringError> {
+        Self::from_move_string_with_base(width, height, s, 1)
+    }
+
+    /// Replay a column-sequence string in which column `c` is written as the
+    /// digit `c + base`. Digits past `9` continue with `a`-`z`, so boards wider
+    /// than nine columns are supported, and `-` is a null move.
+    pub fn from_move_string_with_base(
+        width: u8,
+        height: u8,
+        s: &str,
+        base: u8,
+    ) -> Result<Self, MoveStringError> {
+        let mut game = Game::new(width, height);
+
+        for (ply, ch) in s.chars().enumerate() {
+            let col = if ch == NULL_MOVE_CHAR {
+                None
+            } else {
+                let digit = ch
+                    .to_digit(MOVE_STRING_RADIX)
+                    .filter(|&d| d >= base as u32)
+                    .ok_or(MoveStringError::InvalidCharacter { ply, ch })?;
+                Some((digit - base as u32) as usize)
+            };
+            game.replay_column(ply, col)?;
+        }
+
+        Ok(game)
+    }
+
+    /// Replay a logged game. The rules are validated first, so a corrupt
+    /// record is an error rather than a panic. The recorded outcome is not
+    /// applied: a resigned or agreed game replays as unfinished.
+    pub fn from_record(record: &GameRecord) -> Result<Self, RecordError> {
+        let mut game: Self = GameBuilder::new()
+            .width(record.width)
+            .height(record.height)
+            .connect_len(record.connect_len)
+            .build()
+            .map_err(RecordError::Rules)?;
+
+        for (ply, col) in record.moves.iter().enumerate() {
+            game.replay_column(ply, col.map(usize::from))
+                .map_err(RecordError::Move)?;
         }
+
+        Ok(game)
+    }
+
+    /// Drop a stone into `col` (`None` for a null move) as move `ply` of a
+    /// replay, rejecting anything a real game could not contain.
+    fn replay_column(&mut self, ply: usize, col: Option<usize>) -> Result<(), MoveStringError> {
+        if self.is_over {
+            return Err(MoveStringError::GameOver { ply });
+        }
+        let Some(col) = col else {
+            self.make_null_move();
+            return Ok(());
+        };
+        if col >= self.width() as usize {
+            return Err(MoveStringError::ColumnOutOfRange { ply, col });
+        }
+
+        let col = col as u8;
+        if self.board.is_column_full(col) {
+            return Err(MoveStringError::ColumnFull { ply, col });
+        }
+        let row = self.board.column_height(col, &self.geo);
+        self.make_move(&Move::new(col, row));
+        Ok(())
+    }
+
+    /// A compact summary of the game for training logs, replayable with
+    /// `from_record`.
+    pub fn to_record(&self) -> GameRecord {
+        GameRecord {
+            width: self.width(),
+            height: self.height(),
+            connect_len: self.connect_len(),
+            moves: self
+                .move_history
+                .iter()
+                .map(|m| (!m.is_null()).then_some(m.col))
+                .collect(),
+            outcome: self.outcome,
+        }
+    }
+
+    /// The move history as a column-sequence string with columns numbered
+    /// from 1, e.g. `"4453"`.
+    pub fn to_move_string(&self) -> String {
+        self.to_move_string_with_base(1)
+    }
+
+    /// The move history as a column-sequence string, writing column `c` as the
+    /// digit `c + base` (see `from_move_string_with_base`). Panics if a column
+    /// plus `base` exceeds 35, the largest digit available.
+    pub fn to_move_string_with_base(&self, base: u8) -> String {
+        self.move_history
+            .iter()
+            .map(|move_| {
+                if move_.is_null() {
+                    NULL_MOVE_CHAR
+                } else {
+                    char::from_digit(move_.col as u32 + base as u32, MOVE_STRING_RADIX)
+                        .expect("column does not fit in a move-string digit")
+                }
+            })
+            .collect()
+    }
+
+    /// The `Display` output followed by the move history, one numbered line
+    /// per move (Red's column, then Yellow's), using the board's 0-based
+    /// column labels and `-` for null moves.
+    pub fn to_pretty_string(&self) -> String {
+        let history: String = self
+            .move_history
+            .chunks(2)
+            .enumerate()
+            .map(|(i, pair)| {
+                let cols: String = pair
+                    .iter()
+                    .map(|move_| {
+                        if move_.is_null() {
+                            format!(" {}", NULL_MOVE_CHAR)
+                        } else {
+                            format!(" {}", move_.col)
+                        }
+                    })
+                    .collect();
+                format!("{}.{}\n", i + 1, cols)
+            })
+            .collect();
+        format!("{}Moves:\n{}", self, history)
     }
 
     pub fn width(&self) -> u8 {
@@ -59,8 +516,23 @@ impl<const NW: usize> Game<NW> {
         self.board.get_piece(pos).map(|p| p as i8)
     }
 
+    /// The whole board as `grid[row][col]`, with row 0 at the bottom (the
+    /// same orientation as `Position`).
+    pub fn to_grid(&self) -> Vec<Vec<Option<Player>>> {
+        (0..self.height())
+            .map(|row| {
+                (0..self.width())
+                    .map(|col| self.board.get_piece(&Position::new(col, row)))
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Set or clear a cell directly, bypassing move rules and the history.
+    /// The game-over state is not updated; call `recheck_terminal` afterwards.
     pub fn set_piece(&mut self, pos: &Position, player: Option<Player>) {
-        self.board.set_piece(pos, player)
+        self.board.set_piece(pos, player);
+        self.invalidate_legal_moves();
     }
 
     pub fn board(&self) -> &Board<NW> {
@@ -83,10 +555,89 @@ impl<const NW: usize> Game<NW> {
         self.outcome
     }
 
+    /// The absolute reward (`GameOutcome::encode_winner_absolute`) once the
+    /// game is over, or `None` while it is ongoing, so that an ongoing game is
+    /// not mistaken for a draw's 0.0.
+    pub fn terminal_value(&self) -> Option<f32> {
+        self.outcome.map(|o| o.encode_winner_absolute())
+    }
+
+    /// The winning player, or `None` while the game is ongoing or drawn.
+    pub fn winner(&self) -> Option<Player> {
+        self.outcome.and_then(|o| o.winner())
+    }
+
+    /// Result token: "1-0", "0-1" or "1/2-1/2" once the game is over (see
+    /// `GameOutcome::result_string`), or "*" while it is ongoing.
+    pub fn result_string(&self) -> &'static str {
+        self.outcome.map_or("*", |o| o.result_string())
+    }
+
+    /// Number of plies played so far, counting null moves.
+    pub fn ply(&self) -> usize {
+        self.move_history.len()
+    }
+
+    /// The 1-based move number, where a move is one ply by each side.
+    pub fn move_number(&self) -> usize {
+        self.ply() / 2 + 1
+    }
+
     pub fn move_history(&self) -> &[Move] {
         &self.move_history
     }
 
+    /// The most recent move, with the row it landed on, or `None` before the
+    /// first move. A null move is returned as `Move::NULL`.
+    pub fn last_move(&self) -> Option<Move> {
+        self.move_history.last().copied()
+    }
+
+    /// The direction of the completed line if the game was won on the board,
+    /// otherwise `None`.
+    pub fn win_direction(&self) -> Option<WinDirection> {
+        self.win_direction
+    }
+
+    pub fn connect_len(&self) -> u8 {
+        self.geo.connect_len
+    }
+
+    /// The cells forming the winning line (`connect_len` of them), or `None`
+    /// if the game is ongoing, drawn, or was won by resignation. When the final
+    /// move completed more than one line, the tie-break of
+    /// `BoardGeometry::winning_line` decides which is returned.
+    pub fn winning_line(&self) -> Option<Vec<Position>> {
+        let winner = self.outcome?.winner()?;
+        let line = self.geo.winning_line(&self.board.stones_for(winner))?;
+        Some(
+            line.into_iter()
+                .map(|idx| Position::from_index(idx, self.width()))
+                .collect(),
+        )
+    }
+
+    /// The same game with colors exchanged: every stone changes color, the
+    /// other player is to move and the outcome is mirrored (a Red win becomes a
+    /// Yellow win). The move history is kept as is, so the swapped game reads as
+    /// one in which Yellow moved first.
+    pub fn swap_players(&self) -> Self {
+        Game {
+            board: self.board.swap_colors(),
+            current_player: self.current_player.opposite(),
+            outcome: self.outcome.map(|o| o.swap_players()),
+            ..self.clone()
+        }
+    }
+
+    /// Zobrist key of the canonical board (see `Board::canonical`) with the
+    /// side to move, and whether the board was mirrored to obtain it. A position
+    /// and its mirror image share the same key.
+    pub fn canonical_key(&self) -> (u64, bool) {
+        let (board, mirrored) = self.board.canonical();
+        (board.zobrist_key(self.current_player), mirrored)
+    }
+
     pub fn state_hash(&self) -> StateHash<NW> {
         let recent_history_start = self
             .move_history
@@ -111,21 +662,84 @@ impl<const NW: usize> Game<NW> {
         }
     }
 
+    /// True if both games have the same stones on the board and the same side
+    /// to move, however they got there: move history and how (or whether) the
+    /// game ended are ignored.
+    pub fn same_position(&self, other: &Game<NW>) -> bool {
+        self.current_player == other.current_player
+            && self.board.red_bits() == other.board.red_bits()
+            && self.board.yellow_bits() == other.board.yellow_bits()
+    }
+
     pub fn legal_moves(&self) -> Vec<Move> {
-        if self.is_over {
-            return Vec::new();
+        // Filling the cache here would cost a second allocation for the copy.
+        match self.legal_moves_cache.get() {
+            Some(moves) => moves.clone(),
+            None => self.legal_moves_iter().collect(),
         }
+    }
 
-        let mut moves = Vec::new();
-        for col in 0..self.board.width() {
-            if !self.board.is_column_full(col) {
-                let row = self.board.column_height(col, &self.geo);
-                moves.push(Move::new(col, row));
-            }
-        }
+    /// Legal moves, left to right, computed once and reused until the game is
+    /// next changed. Cheaper than `legal_moves` for repeated queries of the
+    /// same position, as in tree search.
+    pub fn cached_legal_moves(&self) -> &[Move] {
+        self.legal_moves_cache
+            .get_or_init(|| self.legal_moves_iter().collect())
+    }
+
+    fn invalidate_legal_moves(&mut self) {
+        self.legal_moves_cache.take();
+    }
+
+    /// Lazily yield legal moves, left to right, without allocating.
+    pub fn legal_moves_iter(&self) -> impl Iterator<Item = Move> + '_ {
+        let cols = if self.is_over { 0 } else { self.board.width() };
+        (0..cols)
+            .filter(|&col| !self.board.is_column_full(col))
+            .map(|col| Move::new(col, self.board.column_height(col, &self.geo)))
+    }
+
+    /// Legal moves ordered center-first, then alternating outward with the
+    /// left column of each pair first. On even widths the two middle columns
+    /// lead. Useful as a move ordering for alpha-beta search.
+    pub fn legal_moves_ordered(&self) -> Vec<Move> {
+        let width = self.board.width() as i32;
+        let mut moves = self.legal_moves();
+        moves.sort_by_key(|m| ((2 * m.col as i32 - (width - 1)).abs(), m.col));
         moves
     }
 
+    /// Lazily yield each legal move with the game that results from playing
+    /// it. Every child is a fresh clone, so `self` is left untouched.
+    pub fn children(&self) -> impl Iterator<Item = (Move, Game<NW>)> + '_ {
+        self.legal_moves_iter().map(|move_| {
+            let mut child = self.clone();
+            child.make_move(&move_);
+            (move_, child)
+        })
+    }
+
+    /// Fixed-length mask where entry `c` is true iff column `c` is a legal move.
+    /// Entries at or beyond the board width are always false, as is every entry
+    /// once the game is over.
+    pub fn legal_action_mask(&self) -> [bool; MAX_BOARD_DIMENSION as usize] {
+        let mut mask = [false; MAX_BOARD_DIMENSION as usize];
+        for move_ in self.legal_moves_iter() {
+            mask[move_.col as usize] = true;
+        }
+        mask
+    }
+
+    /// Number of legal moves, computed without generating moves.
+    pub fn legal_move_count(&self) -> usize {
+        if self.is_over {
+            return 0;
+        }
+        (0..self.board.width())
+            .filter(|&col| !self.board.is_column_full(col))
+            .count()
+    }
+
     pub fn is_legal_move(&self, move_: &Move) -> bool {
         if self.is_over {
             return false;
@@ -150,13 +764,15 @@ impl<const NW: usize> Game<NW> {
         {
             self.move_history.push(Move::new(move_.col, row));
 
-            // Check for win
-            if self.board.check_win(self.current_player, &self.geo) {
+            // Check for win (only lines through the new stone can have completed)
+            let idx = Position::new(move_.col, row).to_index(self.board.width());
+            if let Some(direction) = self
+                .geo
+                .win_direction_through(&self.board.stones_for(self.current_player), idx)
+            {
                 self.is_over = true;
-                self.outcome = Some(match self.current_player {
-                    Player::Red => GameOutcome::RedWin,
-                    Player::Yellow => GameOutcome::YellowWin,
-                });
+                self.outcome = Some(GameOutcome::win_for(self.current_player));
+                self.win_direction = Some(direction);
             }
             // Check for draw
             else if self.board.is_board_full(&self.geo) {
@@ -166,130 +782,637 @@ impl<const NW: usize> Game<NW> {
 
             // Switch player (always, even if game is over)
             self.current_player = self.current_player.opposite();
+            self.invalidate_legal_moves();
             true
         } else {
             false
         }
     }
 
-    pub fn unmake_move(&mut self) -> bool {
-        if let Some(last_move) = self.move_history.pop() {
-            let pos = Position::new(last_move.col, last_move.row);
-            self.board.set_piece(&pos, None);
+    /// Like `make_move`, but reports why an illegal move was rejected. The
+    /// game is unchanged on error.
+    pub fn try_move(&mut self, move_: &Move) -> Result<(), MoveError> {
+        let landing_row = self.landing_row(move_.col)?;
+        if move_.row != landing_row {
+            return Err(MoveError::WrongRow {
+                col: move_.col,
+                row: move_.row,
+                landing_row,
+            });
+        }
 
-            self.is_over = false;
-            self.outcome = None;
-            self.current_player = self.current_player.opposite();
+        let made = self.make_move(move_);
+        debug_assert!(made);
+        Ok(())
+    }
 
-            true
-        } else {
-            false
-        }
+    /// Drop a stone into `col`, landing on the lowest empty row, and return the
+    /// move played. The game is unchanged on error, which is never
+    /// `MoveError::WrongRow`.
+    pub fn try_drop(&mut self, col: u8) -> Result<Move, MoveError> {
+        let move_ = Move::new(col, self.landing_row(col)?);
+        let made = self.make_move(&move_);
+        debug_assert!(made);
+        Ok(move_)
     }
-}
 
-impl<const NW: usize> Clone for Game<NW> {
-    fn clone(&self) -> Self {
-        Game {
-            board: self.board,
-            geo: self.geo,
-            current_player: self.current_player,
-            move_history: self.move_history.clone(),
-            is_over: self.is_over,
-            outcome: self.outcome,
+    /// The row a stone dropped into `col` would land on, or why it cannot be
+    /// dropped there.
+    fn landing_row(&self, col: u8) -> Result<u8, MoveError> {
+        if self.is_over {
+            return Err(MoveError::GameOver);
+        }
+        if col >= self.board.width() {
+            return Err(MoveError::ColumnOutOfRange(col));
+        }
+        if self.board.is_column_full(col) {
+            return Err(MoveError::ColumnFull(col));
         }
+        Ok(self.board.column_height(col, &self.geo))
     }
-}
 
-impl<const NW: usize> std::fmt::Display for Game<NW> {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(
-            f,
-            "Game(turn: {}, is_over: {}, outcome: {:?})\n{}",
-            self.current_player, self.is_over, self.outcome, self.board
-        )
+    /// Apply `moves` in order, stopping at the first one that is illegal
+    /// (including any move after the game has ended). Returns the number
+    /// applied; the game is left in the state after the last of them.
+    pub fn make_moves(&mut self, moves: &[Move]) -> usize {
+        moves
+            .iter()
+            .take_while(|move_| self.make_move(move_))
+            .count()
     }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::bitboard::nw_for_board;
-    use crate::board::{STANDARD_COLS, STANDARD_ROWS};
-
-    type StdGame = Game<{ nw_for_board(STANDARD_COLS, STANDARD_ROWS) }>;
 
-    fn standard_game() -> StdGame {
-        StdGame::new(STANDARD_COLS, STANDARD_ROWS)
+    /// Legal moves that immediately complete four in a row for the side to move.
+    pub fn winning_moves(&self) -> Vec<Move> {
+        self.winning_moves_for(self.current_player)
     }
 
-    #[test]
-    fn test_new_game() {
-        let game = standard_game();
-        assert_eq!(game.turn(), Player::Red);
-        assert!(!game.is_over());
-        assert!(game.outcome().is_none());
+    /// Legal moves that occupy a cell where the opponent would otherwise
+    /// complete four in a row on their next turn. If the opponent has more
+    /// than one such cell, no single move stops them all.
+    pub fn blocking_moves(&self) -> Vec<Move> {
+        self.winning_moves_for(self.current_player.opposite())
     }
 
-    #[test]
-    fn test_legal_moves_initial() {
-        let game = standard_game();
-        let moves = game.legal_moves();
-        assert_eq!(moves.len(), STANDARD_COLS as usize);
-    }
+    /// Report how `move_` would end the game, without playing it. Returns
+    /// `None` if the move is not legal.
+    pub fn probe_move(&self, move_: &Move) -> Option<MoveResult> {
+        if !self.is_legal_move(move_) {
+            return None;
+        }
 
-    #[test]
-    fn test_make_move() {
-        let mut game = standard_game();
-        let move_ = Move::new(0, 0);
+        let mut board = self.board;
+        let row = board.drop_piece(move_.col, self.current_player, &self.geo)?;
+        let idx = Position::new(move_.col, row).to_index(self.width());
+        let wins = self
+            .geo
+            .has_n_through(&board.stones_for(self.current_player), idx);
+
+        Some(MoveResult {
+            landing_row: row,
+            wins,
+            draws: !wins && board.is_board_full(&self.geo),
+        })
+    }
 
-        assert!(game.is_legal_move(&move_));
-        assert!(game.make_move(&move_));
-        assert_eq!(game.turn(), Player::Yellow);
+    /// The outcome `move_` would produce, without playing it: `None` if the
+    /// move is illegal, `Some(None)` if the game would continue.
+    pub fn outcome_after(&self, move_: &Move) -> Option<Option<GameOutcome>> {
+        let result = self.probe_move(move_)?;
+        Some(if result.wins {
+            Some(GameOutcome::win_for(self.current_player))
+        } else if result.draws {
+            Some(GameOutcome::Draw)
+        } else {
+            None
+        })
     }
 
-    #[test]
-    fn test_make_invalid_move() {
-        let mut game = standard_game();
-        let move_ = Move::new(10, 0); // Invalid column
+    /// Playable cells (the landing cell of each legal move) where a stone of
+    /// `player` would complete a line, regardless of whose turn it is. Empty
+    /// once the game is over.
+    pub fn threat_cells(&self, player: Player) -> Bitboard<NW> {
+        if self.is_over {
+            return Bitboard::empty();
+        }
+        self.geo
+            .completion_cells(&self.board.stones_for(player), &self.board.playable_cells())
+    }
 
-        assert!(!game.is_legal_move(&move_));
-        assert!(!game.make_move(&move_));
+    /// Threat, stone and center counts for a linear evaluator, in one call.
+    /// Threats are zero once the game is over, as for `threat_cells`.
+    pub fn eval_features(&self) -> EvalFeatures {
+        let center = self.geo.center_mask();
+        let center_stones = |player| self.board.stones_for(player).count_masked(&center) as i32;
+        EvalFeatures {
+            red_threats: self.threat_cells(Player::Red).count(),
+            yellow_threats: self.threat_cells(Player::Yellow).count(),
+            red_stones: self.board.count(Player::Red),
+            yellow_stones: self.board.count(Player::Yellow),
+            center_control: center_stones(Player::Red) - center_stones(Player::Yellow),
+        }
     }
 
-    #[test]
-    fn test_unmake_move() {
-        let mut game = standard_game();
-        let move_ = Move::new(0, 0);
+    /// For each legal move, the number of `threat_cells` the mover has after
+    /// playing it. A move that wins outright ends the game and so counts 0;
+    /// look for those with `winning_moves` first.
+    pub fn move_threat_counts(&self) -> Vec<(Move, u32)> {
+        let mover = self.current_player;
+        let mut game = self.clone();
+        self.legal_moves_iter()
+            .map(|move_| {
+                game.make_move(&move_);
+                let count = game.threat_cells(mover).count();
+                game.unmake_move();
+                (move_, count)
+            })
+            .collect()
+    }
 
-        game.make_move(&move_);
-        assert_eq!(game.turn(), Player::Yellow);
+    /// True if `player` has two or more immediate winning moves, regardless of
+    /// whose turn it is. The opponent can block at most one of them.
+    pub fn has_double_threat(&self, player: Player) -> bool {
+        self.winning_moves_for(player).len() >= 2
+    }
 
-        assert!(game.unmake_move());
-        assert_eq!(game.turn(), Player::Red);
-        assert_eq!(game.move_history().len(), 0);
+    /// Legal moves that would complete four in a row if `player` dropped a
+    /// stone there, regardless of whose turn it is.
+    fn winning_moves_for(&self, player: Player) -> Vec<Move> {
+        self.legal_moves()
+            .into_iter()
+            .filter(|move_| {
+                let mut board = self.board;
+                board
+                    .drop_and_check(move_.col, player, &self.geo)
+                    .is_some_and(|(_, won)| won)
+            })
+            .collect()
     }
 
-    #[test]
-    fn test_vertical_win() {
-        let mut game = standard_game();
+    /// True if neither player has room left for `connect_len` in a row: every
+    /// line on the board already holds stones of both colors, so the game can
+    /// only end in a draw. This scans the whole board and is meant as an
+    /// optional early-termination check, not something `make_move` does.
+    pub fn is_dead_draw(&self) -> bool {
+        let empty = self.geo.board_mask.andnot(self.board.occupied_bits());
+        Player::iter().all(|player| {
+            let room = self.board.stones_for(player) | empty;
+            !self.geo.has_n_in_a_row(&room)
+        })
+    }
 
-        for i in 0..3u8 {
-            let red_move = Move::new(0, i);
-            game.make_move(&red_move);
+    /// Play uniformly random legal moves on a copy of the game until it ends,
+    /// and return the outcome. `self` is left untouched; a finished game
+    /// returns its existing outcome. A board filled through `set_piece`
+    /// without `recheck_terminal` has no moves left and counts as a draw.
+    #[cfg(feature = "rand")]
+    pub fn random_playout<R: rand::Rng + ?Sized>(&self, rng: &mut R) -> GameOutcome {
+        use rand::prelude::IteratorRandom;
+
+        let mut game = self.clone();
+        while !game.is_over {
+            let Some(move_) = game.legal_moves_iter().choose(rng) else {
+                break;
+            };
+            game.make_move(&move_);
+        }
+        game.outcome.unwrap_or(GameOutcome::Draw)
+    }
 
-            let yellow_move = Move::new(1, i);
-            game.make_move(&yellow_move);
+    /// Count the leaf nodes of the game tree `depth` plies deep. Finished
+    /// games are leaves even if reached before `depth`. The game is restored to
+    /// its original state before returning.
+    pub fn perft(&mut self, depth: usize) -> u64 {
+        if depth == 0 || self.is_over {
+            return 1;
         }
 
-        let winning_move = Move::new(0, 3);
-        game.make_move(&winning_move);
+        let mut nodes = 0;
+        for move_ in self.legal_moves() {
+            self.make_move(&move_);
+            nodes += self.perft(depth - 1);
+            self.unmake_move();
+        }
+        nodes
+    }
 
-        assert!(game.is_over());
-        assert_eq!(game.outcome(), Some(GameOutcome::RedWin));
+    /// End the game with `player` resigning, awarding the win to the
+    /// opponent. Returns false if the game is already over.
+    ///
+    /// A resignation cannot be taken back: `unmake_move` refuses to undo moves
+    /// once the game has ended this way.
+    pub fn resign(&mut self, player: Player) -> bool {
+        if self.is_over {
+            return false;
+        }
+        self.is_over = true;
+        self.outcome = Some(GameOutcome::resignation_by(player));
+        self.invalidate_legal_moves();
+        true
     }
 
-    #[test]
+    /// End the game as a claimed draw. Returns false if the game is already
+    /// over. Like a resignation, this cannot be undone with `unmake_move`.
+    pub fn claim_draw(&mut self) -> bool {
+        if self.is_over {
+            return false;
+        }
+        self.is_over = true;
+        self.outcome = Some(GameOutcome::ClaimedDraw);
+        self.invalidate_legal_moves();
+        true
+    }
+
+    /// Replace the board with the given stones, validating that the position
+    /// respects gravity and that Red has as many stones as Yellow or one more.
+    /// Yellow moves next if Red is ahead and Red otherwise, the move history
+    /// is cleared and the game-over state is recomputed from the board.
+    ///
+    /// On error the game is left unchanged.
+    pub fn place_stones(
+        &mut self,
+        reds: &[Position],
+        yellows: &[Position],
+    ) -> Result<(), PlacementError> {
+        let (width, height) = (self.width(), self.height());
+        let mut board = Board::new(width, height);
+
+        let stones = reds
+            .iter()
+            .map(|pos| (pos, Player::Red))
+            .chain(yellows.iter().map(|pos| (pos, Player::Yellow)));
+        for (pos, player) in stones {
+            if !pos.is_valid(width, height) {
+                return Err(PlacementError::OutOfBounds(*pos));
+            }
+            if board.get_piece(pos).is_some() {
+                return Err(PlacementError::DuplicatePosition(*pos));
+            }
+            board.set_piece(pos, Some(player));
+        }
+
+        for pos in reds.iter().chain(yellows) {
+            if pos.row > 0
+                && board
+                    .get_piece(&Position::new(pos.col, pos.row - 1))
+                    .is_none()
+            {
+                return Err(PlacementError::FloatingStone(*pos));
+            }
+        }
+
+        if reds.len() != yellows.len() && reds.len() != yellows.len() + 1 {
+            return Err(PlacementError::InvalidStoneCounts {
+                reds: reds.len(),
+                yellows: yellows.len(),
+            });
+        }
+
+        let current_player = if reds.len() > yellows.len() {
+            Player::Yellow
+        } else {
+            Player::Red
+        };
+        let (outcome, win_direction) = outcome_from_board(&board, &self.geo, current_player);
+
+        self.board = board;
+        self.current_player = current_player;
+        self.move_history.clear();
+        self.is_over = outcome.is_some();
+        self.outcome = outcome;
+        self.win_direction = win_direction;
+        self.invalidate_legal_moves();
+        Ok(())
+    }
+
+    /// Recompute `is_over` and `outcome` from the stones on the board: a win
+    /// for either player (the one who moved last is checked first), otherwise a
+    /// draw if the board is full. Use after editing the board with `set_piece`.
+    ///
+    /// A resignation or claimed draw is kept unless the board itself now
+    /// decides the game.
+    pub fn recheck_terminal(&mut self) {
+        let (outcome, win_direction) =
+            outcome_from_board(&self.board, &self.geo, self.current_player);
+        let outcome = outcome.or(self.outcome.filter(|o| !o.is_decided_on_board()));
+
+        self.is_over = outcome.is_some();
+        self.outcome = outcome;
+        self.win_direction = win_direction;
+        self.invalidate_legal_moves();
+    }
+
+    /// Pass the turn to the opponent without placing a stone, recording
+    /// `Move::NULL` in the history. Intended for analysis such as null-move
+    /// pruning; the game must not be over.
+    pub fn make_null_move(&mut self) {
+        assert!(!self.is_over, "Cannot make a null move in a finished game");
+        self.move_history.push(Move::NULL);
+        self.current_player = self.current_player.opposite();
+    }
+
+    /// Undo a null move made with `make_null_move`. The last history entry
+    /// must be a null move.
+    pub fn unmake_null_move(&mut self) {
+        assert!(
+            self.move_history.last().is_some_and(|m| m.is_null()),
+            "Last move is not a null move"
+        );
+        self.unmake_move();
+    }
+
+    /// Undo the last move, including null moves. Returns false if there is no
+    /// move to undo, or if the game ended by resignation or claimed draw.
+    pub fn unmake_move(&mut self) -> bool {
+        if self.outcome.is_some_and(|o| !o.is_decided_on_board()) {
+            return false;
+        }
+
+        if let Some(last_move) = self.move_history.pop() {
+            if !last_move.is_null() {
+                self.board.set_piece(&last_move.position(), None);
+            }
+
+            self.is_over = false;
+            self.outcome = None;
+            self.win_direction = None;
+            self.current_player = self.current_player.opposite();
+            self.invalidate_legal_moves();
+
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Undo up to `n` moves, stopping early if the history runs out or a move
+    /// cannot be undone (see `unmake_move`). Returns the number undone.
+    pub fn unmake_moves(&mut self, n: usize) -> usize {
+        let mut undone = 0;
+        while undone < n && self.unmake_move() {
+            undone += 1;
+        }
+        undone
+    }
+
+    /// Undo moves until the history is `ply` moves long. Returns false if
+    /// `ply` is beyond the current history, or if the moves cannot be undone
+    /// because the game was resigned or a draw was claimed; nothing is undone
+    /// in either case.
+    pub fn undo_to_ply(&mut self, ply: usize) -> bool {
+        let Some(n) = self.move_history.len().checked_sub(ply) else {
+            return false;
+        };
+        if n > 0 && self.outcome.is_some_and(|o| !o.is_decided_on_board()) {
+            return false;
+        }
+        self.unmake_moves(n) == n
+    }
+}
+
+/// The outcome implied by the stones on the board, with the direction of the
+/// winning line if there is one. The player who made the last move (the
+/// opponent of `current_player`) is checked first, matching `make_move`.
+fn outcome_from_board<const NW: usize>(
+    board: &Board<NW>,
+    geo: &BoardGeometry<NW>,
+    current_player: Player,
+) -> (Option<GameOutcome>, Option<WinDirection>) {
+    for player in [current_player.opposite(), current_player] {
+        if let Some(direction) = geo.winning_direction(&board.stones_for(player)) {
+            return (Some(GameOutcome::win_for(player)), Some(direction));
+        }
+    }
+    if board.is_board_full(geo) {
+        (Some(GameOutcome::Draw), None)
+    } else {
+        (None, None)
+    }
+}
+
+/// One game as written to a training log by `Game::to_record`: the rules, the
+/// 0-based column of each move (`None` for a null move) and the outcome
+/// (`None` if unfinished). Replay it with `Game::from_record`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GameRecord {
+    pub width: u8,
+    pub height: u8,
+    #[cfg_attr(feature = "serde", serde(default = "default_connect_len"))]
+    pub connect_len: u8,
+    pub moves: Vec<Option<u8>>,
+    pub outcome: Option<GameOutcome>,
+}
+
+/// Serialized form of a `Game`. Geometry and game-over state are not stored;
+/// they are rebuilt from the board on deserialization.
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct GameData<const NW: usize> {
+    board: Board<NW>,
+    #[serde(default = "default_connect_len")]
+    connect_len: u8,
+    current_player: Player,
+    move_history: Vec<Move>,
+    /// Only set for outcomes that cannot be recovered from the board
+    /// (resignations and claimed draws).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    adjudication: Option<GameOutcome>,
+}
+
+#[cfg(feature = "serde")]
+fn default_connect_len() -> u8 {
+    DEFAULT_CONNECT_LEN
+}
+
+#[cfg(feature = "serde")]
+impl<const NW: usize> From<Game<NW>> for GameData<NW> {
+    fn from(game: Game<NW>) -> Self {
+        GameData {
+            board: game.board,
+            connect_len: game.geo.connect_len,
+            current_player: game.current_player,
+            move_history: game.move_history,
+            adjudication: game.outcome.filter(|o| !o.is_decided_on_board()),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<const NW: usize> TryFrom<GameData<NW>> for Game<NW> {
+    type Error = String;
+
+    fn try_from(data: GameData<NW>) -> Result<Self, Self::Error> {
+        let board = data.board;
+        if !(MIN_CONNECT_LEN..=MAX_CONNECT_LEN).contains(&data.connect_len) {
+            return Err(format!(
+                "Connection length {} must be between {} and {}",
+                data.connect_len, MIN_CONNECT_LEN, MAX_CONNECT_LEN
+            ));
+        }
+        let geo = BoardGeometry::with_connect_len(board.width(), board.height(), data.connect_len);
+
+        // Players alternate through the history (null moves included) and
+        // end with `current_player` to move, so each stone played must be
+        // the mover's colour, and no cell can be played twice.
+        let mut mover = if data.move_history.len().is_multiple_of(2) {
+            data.current_player
+        } else {
+            data.current_player.opposite()
+        };
+        let mut played = Bitboard::<NW>::empty();
+        for move_ in &data.move_history {
+            if !move_.is_null() {
+                let pos = move_.position();
+                if !pos.is_valid(board.width(), board.height()) {
+                    return Err(format!("Move history contains off-board cell {}", move_));
+                }
+                if board.get_piece(&pos) != Some(mover) {
+                    return Err(format!(
+                        "Move history has {} playing {}, but the cell does not hold a {} stone",
+                        mover, move_, mover
+                    ));
+                }
+                let idx = pos.to_index(board.width());
+                if played.get(idx) {
+                    return Err(format!("Move history plays {} twice", move_));
+                }
+                played.set(idx);
+            }
+            mover = mover.opposite();
+        }
+
+        let (outcome, win_direction) = outcome_from_board(&board, &geo, data.current_player);
+
+        let outcome = match (outcome, data.adjudication) {
+            (None, Some(adjudication)) if !adjudication.is_decided_on_board() => Some(adjudication),
+            (_, Some(adjudication)) => {
+                return Err(format!(
+                    "Adjudicated outcome {} is inconsistent with the board",
+                    adjudication
+                ))
+            }
+            (outcome, None) => outcome,
+        };
+
+        Ok(Game {
+            board,
+            geo,
+            current_player: data.current_player,
+            move_history: data.move_history,
+            is_over: outcome.is_some(),
+            outcome,
+            win_direction,
+            legal_moves_cache: LegalMovesCache::new(),
+        })
+    }
+}
+
+impl<const NW: usize> Clone for Game<NW> {
+    fn clone(&self) -> Self {
+        Game {
+            board: self.board,
+            geo: self.geo,
+            current_player: self.current_player,
+            move_history: self.move_history.clone(),
+            is_over: self.is_over,
+            outcome: self.outcome,
+            win_direction: self.win_direction,
+            // Left empty so cloning stays allocation-free beyond the history;
+            // children are about to be mutated anyway.
+            legal_moves_cache: LegalMovesCache::new(),
+        }
+    }
+}
+
+impl<const NW: usize> core::fmt::Display for Game<NW> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "Game(turn: {}, is_over: {}, outcome: {:?})\n{}",
+            self.current_player, self.is_over, self.outcome, self.board
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bitboard::nw_for_board;
+    use crate::board::{STANDARD_COLS, STANDARD_ROWS};
+
+    type StdGame = Game<{ nw_for_board(STANDARD_COLS, STANDARD_ROWS) }>;
+
+    fn standard_game() -> StdGame {
+        StdGame::new(STANDARD_COLS, STANDARD_ROWS)
+    }
+
+    #[test]
+    fn test_new_game() {
+        let game = standard_game();
+        assert_eq!(game.turn(), Player::Red);
+        assert!(!game.is_over());
+        assert!(game.outcome().is_none());
+    }
+
+    #[test]
+    fn test_legal_moves_initial() {
+        let game = standard_game();
+        let moves = game.legal_moves();
+        assert_eq!(moves.len(), STANDARD_COLS as usize);
+    }
+
+    #[test]
+    fn test_make_move() {
+        let mut game = standard_game();
+        let move_ = Move::new(0, 0);
+
+        assert!(game.is_legal_move(&move_));
+        assert!(game.make_move(&move_));
+        assert_eq!(game.turn(), Player::Yellow);
+    }
+
+    #[test]
+    fn test_make_invalid_move() {
+        let mut game = standard_game();
+        let move_ = Move::new(10, 0); // Invalid column
+
+        assert!(!game.is_legal_move(&move_));
+        assert!(!game.make_move(&move_));
+    }
+
+    #[test]
+    fn test_unmake_move() {
+        let mut game = standard_game();
+        let move_ = Move::new(0, 0);
+
+        game.make_move(&move_);
+        assert_eq!(game.turn(), Player::Yellow);
+
+        assert!(game.unmake_move());
+        assert_eq!(game.turn(), Player::Red);
+        assert_eq!(game.move_history().len(), 0);
+    }
+
+    #[test]
+    fn test_vertical_win() {
+        let mut game = standard_game();
+
+        for i in 0..3u8 {
+            let red_move = Move::new(0, i);
+            game.make_move(&red_move);
+
+            let yellow_move = Move::new(1, i);
+            game.make_move(&yellow_move);
+        }
+
+        let winning_move = Move::new(0, 3);
+        game.make_move(&winning_move);
+
+        assert!(game.is_over());
+        assert_eq!(game.outcome(), Some(GameOutcome::RedWin));
+    }
+
+    #[test]
     fn test_horizontal_win() {
         let mut game = standard_game();
 
@@ -389,6 +1512,37 @@ mod tests {
         assert_eq!(game.legal_moves().len(), 0);
     }
 
+    fn mask_from_legal_moves(game: &StdGame) -> [bool; 32] {
+        let mut mask = [false; 32];
+        for move_ in game.legal_moves() {
+            mask[move_.col as usize] = true;
+        }
+        mask
+    }
+
+    #[test]
+    fn test_legal_action_mask() {
+        let mut game = standard_game();
+        let mask = game.legal_action_mask();
+        assert_eq!(mask, mask_from_legal_moves(&game));
+        assert!(mask[..STANDARD_COLS as usize].iter().all(|&legal| legal));
+        assert!(mask[STANDARD_COLS as usize..].iter().all(|&legal| !legal));
+
+        // Fill column 0 without anyone winning
+        for i in 0..STANDARD_ROWS {
+            game.make_move(&Move::new(0, i));
+        }
+        let mask = game.legal_action_mask();
+        assert_eq!(mask, mask_from_legal_moves(&game));
+        assert!(!mask[0]);
+        assert!(mask[1]);
+
+        // Red completes a vertical four in column 1
+        play_columns(&mut game, &[1, 2, 1, 2, 1, 2, 1]);
+        assert!(game.is_over());
+        assert_eq!(game.legal_action_mask(), [false; 32]);
+    }
+
     #[test]
     fn test_is_legal_move_after_column_full() {
         let mut game = standard_game();
@@ -431,4 +1585,1657 @@ mod tests {
         let mut game = standard_game();
         assert!(!game.unmake_move());
     }
+
+    fn positions(cells: &[(u8, u8)]) -> Option<Vec<Position>> {
+        Some(
+            cells
+                .iter()
+                .map(|&(col, row)| Position::new(col, row))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn test_winning_line_none_when_ongoing() {
+        let mut game = standard_game();
+        assert_eq!(game.winning_line(), None);
+
+        game.make_move(&Move::new(3, 0));
+        assert_eq!(game.winning_line(), None);
+    }
+
+    #[test]
+    fn test_winning_line_horizontal() {
+        let mut game = standard_game();
+
+        for col in 0..3u8 {
+            game.make_move(&Move::new(col, 0));
+            game.make_move(&Move::new(col, 1));
+        }
+        game.make_move(&Move::new(3, 0));
+
+        assert_eq!(
+            game.winning_line(),
+            positions(&[(0, 0), (1, 0), (2, 0), (3, 0)])
+        );
+    }
+
+    #[test]
+    fn test_winning_line_vertical() {
+        let mut game = standard_game();
+
+        for i in 0..3u8 {
+            game.make_move(&Move::new(0, i));
+            game.make_move(&Move::new(1, i));
+        }
+        game.make_move(&Move::new(0, 3));
+
+        assert_eq!(
+            game.winning_line(),
+            positions(&[(0, 0), (0, 1), (0, 2), (0, 3)])
+        );
+    }
+
+    #[test]
+    fn test_winning_line_diagonals() {
+        // Ascending: Red on (0,0), (1,1), (2,2), (3,3)
+        let mut game = standard_game();
+        for &col in &[0u8, 1, 1, 2, 2, 3, 2, 3, 3, 6, 3] {
+            let row = game.board().column_height(col, game.geo());
+            game.make_move(&Move::new(col, row));
+        }
+        assert_eq!(game.outcome(), Some(GameOutcome::RedWin));
+        assert_eq!(
+            game.winning_line(),
+            positions(&[(0, 0), (1, 1), (2, 2), (3, 3)])
+        );
+
+        // Descending: Red on (3,0), (2,1), (1,2), (0,3)
+        let mut game = standard_game();
+        for &col in &[3u8, 2, 2, 1, 1, 0, 1, 0, 0, 6, 0] {
+            let row = game.board().column_height(col, game.geo());
+            game.make_move(&Move::new(col, row));
+        }
+        assert_eq!(game.outcome(), Some(GameOutcome::RedWin));
+        assert_eq!(
+            game.winning_line(),
+            positions(&[(3, 0), (2, 1), (1, 2), (0, 3)])
+        );
+    }
+
+    #[test]
+    fn test_winning_line_double() {
+        let mut game = standard_game();
+
+        // Red: col 3 rows 0..3 and row 3 cols 0..3, supported by Yellow stones.
+        for col in 0..3u8 {
+            for row in 0..3u8 {
+                game.set_piece(&Position::new(col, row), Some(Player::Yellow));
+            }
+            game.set_piece(&Position::new(col, 3), Some(Player::Red));
+        }
+        for row in 0..3u8 {
+            game.set_piece(&Position::new(3, row), Some(Player::Red));
+        }
+
+        // Completes both a horizontal and a vertical four; horizontal is reported.
+        assert!(game.make_move(&Move::new(3, 3)));
+        assert_eq!(game.outcome(), Some(GameOutcome::RedWin));
+        assert_eq!(
+            game.winning_line(),
+            positions(&[(0, 3), (1, 3), (2, 3), (3, 3)])
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    fn serde_round_trip<const NW: usize>(width: u8, height: u8, cols: &[u8]) {
+        let mut game = Game::<NW>::new(width, height);
+        for &col in cols {
+            let row = game.board().column_height(col, game.geo());
+            assert!(game.make_move(&Move::new(col, row)));
+        }
+
+        let json = serde_json::to_string(&game).expect("serde_round_trip: serialize failed");
+        let restored: Game<NW> =
+            serde_json::from_str(&json).expect("serde_round_trip: deserialize failed");
+
+        assert_eq!(restored.board(), game.board());
+        assert_eq!(restored.geo(), game.geo());
+        assert_eq!(restored.turn(), game.turn());
+        assert_eq!(restored.move_history(), game.move_history());
+        assert_eq!(restored.is_over(), game.is_over());
+        assert_eq!(restored.outcome(), game.outcome());
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_serde_round_trip_7x6() {
+        serde_round_trip::<{ nw_for_board(7, 6) }>(7, 6, &[3, 3, 2, 4, 6]);
+        // Finished game: Red wins vertically in column 0
+        serde_round_trip::<{ nw_for_board(7, 6) }>(7, 6, &[0, 1, 0, 1, 0, 1, 0]);
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_serde_round_trip_9x9() {
+        serde_round_trip::<{ nw_for_board(9, 9) }>(9, 9, &[0, 8, 4, 4, 4, 7, 1]);
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_serde_rejects_mismatched_dimensions() {
+        let game = Game::<{ nw_for_board(9, 9) }>::new(9, 9);
+        let json = serde_json::to_string(&game).expect("serialize failed");
+
+        let result = serde_json::from_str::<StdGame>(&json);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_serde_rejects_invalid_board() {
+        // Overlapping red and yellow stones
+        let json = r#"{"board":{"width":7,"height":6,"red":[1],"yellow":[1]},"current_player":"Red","move_history":[]}"#;
+        assert!(serde_json::from_str::<StdGame>(json).is_err());
+
+        // Stone beyond the 42-cell board
+        let json = r#"{"board":{"width":7,"height":6,"red":[4398046511104],"yellow":[0]},"current_player":"Red","move_history":[]}"#;
+        assert!(serde_json::from_str::<StdGame>(json).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_serde_rejects_inconsistent_history() {
+        let mut game = standard_game();
+        play_columns(&mut game, &[3, 4]);
+        let json = serde_json::to_string(&game).expect("serialize failed");
+        assert!(serde_json::from_str::<StdGame>(&json).is_ok());
+
+        // Red to move after two plies, not Yellow.
+        let wrong_turn = json.replace(r#""current_player":"Red""#, r#""current_player":"Yellow""#);
+        assert_ne!(wrong_turn, json);
+        assert!(serde_json::from_str::<StdGame>(&wrong_turn).is_err());
+
+        // An extra null move between the stones puts the later ones on the
+        // wrong player.
+        let mut nulled = standard_game();
+        play_columns(&mut nulled, &[3, 4]);
+        nulled.make_null_move();
+        let json = serde_json::to_string(&nulled).expect("serialize failed");
+        assert!(serde_json::from_str::<StdGame>(&json).is_ok());
+        let shifted = json.replace(
+            r#"{"col":3,"row":0},"#,
+            r#"{"col":3,"row":0},{"col":255,"row":255},"#,
+        );
+        assert_ne!(shifted, json);
+        assert!(serde_json::from_str::<StdGame>(&shifted).is_err());
+
+        // The same cell played twice.
+        let mut twice = standard_game();
+        play_columns(&mut twice, &[3, 4, 3]);
+        let json = serde_json::to_string(&twice).expect("serialize failed");
+        let repeated = json.replace(r#"{"col":3,"row":1}"#, r#"{"col":3,"row":0}"#);
+        assert_ne!(repeated, json);
+        assert!(serde_json::from_str::<StdGame>(&repeated).is_err());
+
+        // Connection lengths outside the geometry's range.
+        let json = serde_json::to_string(&standard_game()).expect("serialize failed");
+        for connect_len in [1, MAX_CONNECT_LEN + 1] {
+            let bad = json.replace(
+                r#""connect_len":4"#,
+                &format!(r#""connect_len":{}"#, connect_len),
+            );
+            assert_ne!(bad, json);
+            assert!(serde_json::from_str::<StdGame>(&bad).is_err());
+        }
+    }
+
+    #[test]
+    fn test_perft_standard() {
+        let mut game = standard_game();
+
+        // No column fills and no game ends before ply 7, so counts are 7^depth.
+        assert_eq!(game.perft(0), 1);
+        assert_eq!(game.perft(1), 7);
+        assert_eq!(game.perft(2), 49);
+        assert_eq!(game.perft(3), 343);
+        assert_eq!(game.perft(4), 2_401);
+        assert_eq!(game.perft(5), 16_807);
+        assert_eq!(game.perft(6), 117_649);
+        // At ply 7 the 7 single-column sequences are impossible (column height 6).
+        assert_eq!(game.perft(7), 823_536);
+    }
+
+    #[test]
+    fn test_perft_restores_state() {
+        let mut game = standard_game();
+        game.make_move(&Move::new(3, 0));
+        game.make_move(&Move::new(3, 1));
+
+        let before = game.clone();
+        game.perft(4);
+
+        assert_eq!(game.board(), before.board());
+        assert_eq!(game.turn(), before.turn());
+        assert_eq!(game.move_history(), before.move_history());
+        assert_eq!(game.outcome(), before.outcome());
+    }
+
+    #[test]
+    fn test_perft_terminal_is_leaf() {
+        let mut game = standard_game();
+        for i in 0..3u8 {
+            game.make_move(&Move::new(0, i));
+            game.make_move(&Move::new(1, i));
+        }
+        game.make_move(&Move::new(0, 3));
+
+        assert!(game.is_over());
+        assert_eq!(game.perft(3), 1);
+    }
+
+    fn play_columns<const NW: usize>(game: &mut Game<NW>, cols: &[u8]) {
+        for &col in cols {
+            let row = game.board().column_height(col, game.geo());
+            assert!(game.make_move(&Move::new(col, row)));
+        }
+    }
+
+    #[test]
+    fn test_winning_moves_single() {
+        let mut game = standard_game();
+        // Red: col 0 rows 0..3, Yellow: col 1 rows 0..3. Red to move.
+        play_columns(&mut game, &[0, 1, 0, 1, 0, 1]);
+
+        let before = game.clone();
+        assert_eq!(game.winning_moves(), vec![Move::new(0, 3)]);
+        assert_eq!(game.board(), before.board());
+        assert_eq!(game.move_history(), before.move_history());
+        assert_eq!(game.turn(), before.turn());
+    }
+
+    #[test]
+    fn test_winning_moves_multiple() {
+        let mut game = standard_game();
+        // Red: row 0 cols 1..4, Yellow stacked on top. Red to move with both ends open.
+        play_columns(&mut game, &[1, 1, 2, 2, 3, 3]);
+
+        assert_eq!(game.winning_moves(), vec![Move::new(0, 0), Move::new(4, 0)]);
+    }
+
+    #[test]
+    fn test_winning_moves_none() {
+        let mut game = standard_game();
+        assert!(game.winning_moves().is_empty());
+
+        play_columns(&mut game, &[3, 3, 2, 4]);
+        assert!(game.winning_moves().is_empty());
+    }
+
+    #[test]
+    fn test_blocking_moves() {
+        let mut game = standard_game();
+        // Red: col 0 rows 0..3, Yellow: cols 1 and 2. Yellow to move must block col 0.
+        play_columns(&mut game, &[0, 1, 0, 2, 0]);
+
+        assert_eq!(game.blocking_moves(), vec![Move::new(0, 3)]);
+        assert!(game.winning_moves().is_empty());
+
+        game.make_move(&Move::new(0, 3));
+        assert!(game.blocking_moves().is_empty());
+    }
+
+    #[test]
+    fn test_probe_move() {
+        let mut game = standard_game();
+        play_columns(&mut game, &[0, 1, 0, 1, 0, 1]);
+        let before = game.state_hash();
+
+        assert_eq!(
+            game.probe_move(&Move::new(0, 3)),
+            Some(MoveResult {
+                landing_row: 3,
+                wins: true,
+                draws: false
+            })
+        );
+        assert_eq!(
+            game.probe_move(&Move::new(4, 0)),
+            Some(MoveResult {
+                landing_row: 0,
+                wins: false,
+                draws: false
+            })
+        );
+        assert_eq!(game.probe_move(&Move::new(4, 1)), None);
+        assert_eq!(game.probe_move(&Move::new(9, 0)), None);
+        assert_eq!(game.state_hash(), before);
+    }
+
+    #[test]
+    fn test_probe_move_draw() {
+        let mut game = standard_game();
+        play_columns(
+            &mut game,
+            &[
+                0, 1, 2, 0, 1, 2, 0, 1, 2, 0, 1, 2, 0, 1, 2, 0, 1, 2, // Cols 0-2
+                3, 4, 5, 3, 4, 5, 3, 4, 5, 3, 4, 5, 3, 4, 5, 3, 4, 5, // Cols 3-5
+                6, 6, 6, 6, 6, // Col 6, one cell left
+            ],
+        );
+
+        assert_eq!(
+            game.probe_move(&Move::new(6, 5)),
+            Some(MoveResult {
+                landing_row: 5,
+                wins: false,
+                draws: true
+            })
+        );
+        assert!(!game.is_over());
+    }
+
+    #[test]
+    fn test_threat_cells_matches_simulation() {
+        use rand::prelude::IndexedRandom;
+        use rand::SeedableRng;
+
+        let mut rng = rand::rngs::SmallRng::seed_from_u64(40);
+        for connect_len in [3, 4, 5] {
+            for _ in 0..30 {
+                let mut game = StdGame::with_connect_len(STANDARD_COLS, STANDARD_ROWS, connect_len);
+                while !game.is_over() {
+                    for player in [Player::Red, Player::Yellow] {
+                        let mut expected = Bitboard::empty();
+                        for move_ in game.winning_moves_for(player) {
+                            expected.set(move_.position().to_index(game.width()));
+                        }
+                        assert_eq!(game.threat_cells(player), expected);
+                    }
+
+                    let moves = game.legal_moves();
+                    let move_ = moves
+                        .choose(&mut rng)
+                        .expect("test_threat_cells_matches_simulation: no legal moves");
+                    game.make_move(move_);
+                }
+                assert!(game.threat_cells(Player::Red).is_empty());
+            }
+        }
+    }
+
+    #[test]
+    fn test_threat_cells_open_three() {
+        let mut game = standard_game();
+        play_columns(&mut game, &[2, 2, 3, 3, 4]);
+
+        let cells: Vec<usize> = game.threat_cells(Player::Red).iter_ones().collect();
+        assert_eq!(cells, vec![1, 5]);
+        assert!(game.threat_cells(Player::Yellow).is_empty());
+    }
+
+    #[test]
+    fn test_has_double_threat_open_three() {
+        let mut game = standard_game();
+        // Red: cols 2..=4 on row 0, open at both ends. Yellow stacks on top.
+        play_columns(&mut game, &[2, 2, 3, 3, 4]);
+
+        assert_eq!(game.turn(), Player::Yellow);
+        assert!(game.has_double_threat(Player::Red));
+        assert!(!game.has_double_threat(Player::Yellow));
+        assert_eq!(
+            game.blocking_moves(),
+            vec![Move::new(1, 0), Move::new(5, 0)]
+        );
+    }
+
+    #[test]
+    fn test_has_double_threat_single_threat() {
+        let mut game = standard_game();
+        // Red: cols 0..=2 on row 0, completable only at col 3
+        play_columns(&mut game, &[0, 0, 1, 1, 2]);
+
+        assert!(!game.has_double_threat(Player::Red));
+        assert_eq!(game.blocking_moves(), vec![Move::new(3, 0)]);
+    }
+
+    #[test]
+    fn test_winning_and_blocking_moves_when_over() {
+        let mut game = standard_game();
+        play_columns(&mut game, &[0, 1, 0, 1, 0, 1, 0]);
+
+        assert!(game.is_over());
+        assert!(game.winning_moves().is_empty());
+        assert!(game.blocking_moves().is_empty());
+    }
+
+    #[test]
+    fn test_from_move_string_known_opening() {
+        let game = StdGame::from_move_string(STANDARD_COLS, STANDARD_ROWS, "4453")
+            .expect("test_from_move_string_known_opening: parse failed");
+
+        assert_eq!(
+            game.move_history(),
+            &[
+                Move::new(3, 0),
+                Move::new(3, 1),
+                Move::new(4, 0),
+                Move::new(2, 0)
+            ]
+        );
+        assert_eq!(
+            game.get_piece(&Position::new(3, 1)),
+            Some(Player::Yellow as i8)
+        );
+        assert_eq!(game.turn(), Player::Red);
+        assert_eq!(game.to_move_string(), "4453");
+    }
+
+    #[test]
+    fn test_move_string_round_trip() {
+        use rand::prelude::IndexedRandom;
+        use rand::SeedableRng;
+
+        let mut rng = rand::rngs::SmallRng::seed_from_u64(36);
+        for _ in 0..20 {
+            let mut game = standard_game();
+            while !game.is_over() {
+                let moves = game.legal_moves();
+                let move_ = moves
+                    .choose(&mut rng)
+                    .expect("test_move_string_round_trip: no legal moves");
+                game.make_move(move_);
+            }
+
+            for base in [0, 1] {
+                let s = game.to_move_string_with_base(base);
+                let restored =
+                    StdGame::from_move_string_with_base(STANDARD_COLS, STANDARD_ROWS, &s, base)
+                        .expect("test_move_string_round_trip: parse failed");
+                assert_eq!(restored.state_hash(), game.state_hash());
+                assert_eq!(restored.move_history(), game.move_history());
+            }
+        }
+    }
+
+    #[test]
+    fn test_move_string_wide_board_and_null_move() {
+        type WideGame = Game<{ nw_for_board(12, 4) }>;
+        let mut game = WideGame::new(12, 4);
+        game.make_move(&Move::new(11, 0));
+        game.make_null_move();
+        game.make_move(&Move::new(9, 0));
+
+        let s = game.to_move_string();
+        assert_eq!(s, "c-a");
+        let restored = WideGame::from_move_string(12, 4, &s)
+            .expect("test_move_string_wide_board_and_null_move: parse failed");
+        assert_eq!(restored.move_history(), game.move_history());
+    }
+
+    #[test]
+    fn test_from_move_string_errors() {
+        let parse = |s: &str| StdGame::from_move_string(STANDARD_COLS, STANDARD_ROWS, s).err();
+
+        assert_eq!(
+            parse("448"),
+            Some(MoveStringError::ColumnOutOfRange { ply: 2, col: 7 })
+        );
+        assert_eq!(
+            parse("40"),
+            Some(MoveStringError::InvalidCharacter { ply: 1, ch: '0' })
+        );
+        assert_eq!(
+            parse("4 4"),
+            Some(MoveStringError::InvalidCharacter { ply: 1, ch: ' ' })
+        );
+        assert_eq!(
+            parse("1111111"),
+            Some(MoveStringError::ColumnFull { ply: 6, col: 0 })
+        );
+        assert_eq!(
+            parse("12121213"),
+            Some(MoveStringError::GameOver { ply: 7 })
+        );
+    }
+
+    #[test]
+    fn test_ply_and_move_number() {
+        let mut game = standard_game();
+        assert_eq!((game.ply(), game.move_number()), (0, 1));
+
+        play_columns(&mut game, &[3]);
+        assert_eq!((game.ply(), game.move_number()), (1, 1));
+        play_columns(&mut game, &[3, 4]);
+        assert_eq!((game.ply(), game.move_number()), (3, 2));
+
+        game.make_null_move();
+        assert_eq!((game.ply(), game.move_number()), (4, 3));
+
+        game.unmake_move();
+        game.unmake_move();
+        assert_eq!((game.ply(), game.move_number()), (2, 2));
+    }
+
+    #[test]
+    fn test_result_string() {
+        let mut game = standard_game();
+        assert_eq!(game.result_string(), "*");
+
+        play_columns(&mut game, &[0, 1, 0, 1, 0, 1, 0]);
+        assert_eq!(game.result_string(), "1-0");
+
+        let mut game = standard_game();
+        play_columns(&mut game, &[6, 0, 1, 0, 1, 0, 1, 0]);
+        assert_eq!(game.result_string(), "0-1");
+
+        let mut game = standard_game();
+        play_columns(
+            &mut game,
+            &[
+                0, 1, 2, 0, 1, 2, 0, 1, 2, 0, 1, 2, 0, 1, 2, 0, 1, 2, // Cols 0-2
+                3, 4, 5, 3, 4, 5, 3, 4, 5, 3, 4, 5, 3, 4, 5, 3, 4, 5, // Cols 3-5
+                6, 6, 6, 6, 6, 6, // Col 6
+            ],
+        );
+        assert_eq!(game.outcome(), Some(GameOutcome::Draw));
+        assert_eq!(game.result_string(), "1/2-1/2");
+    }
+
+    #[test]
+    fn test_swap_players() {
+        let mut game = standard_game();
+        play_columns(&mut game, &[0, 1, 0, 1, 0, 1, 0]);
+        assert_eq!(game.outcome(), Some(GameOutcome::RedWin));
+
+        let swapped = game.swap_players();
+        assert_eq!(swapped.outcome(), Some(GameOutcome::YellowWin));
+        assert_eq!(swapped.turn(), Player::Red);
+        assert_eq!(swapped.win_direction(), Some(WinDirection::Vertical));
+        assert_eq!(
+            swapped.get_piece(&Position::new(0, 0)),
+            Some(Player::Yellow as i8)
+        );
+        assert_eq!(
+            swapped.winning_line(),
+            positions(&[(0, 0), (0, 1), (0, 2), (0, 3)])
+        );
+
+        let back = swapped.swap_players();
+        assert_eq!(back.state_hash(), game.state_hash());
+    }
+
+    #[test]
+    fn test_canonical_key() {
+        let mut game = standard_game();
+        let mut mirrored = standard_game();
+        play_columns(&mut game, &[0, 2, 5]);
+        play_columns(&mut mirrored, &[6, 4, 1]);
+
+        let (key, flipped) = game.canonical_key();
+        let (mirrored_key, mirrored_flipped) = mirrored.canonical_key();
+        assert_eq!(key, mirrored_key);
+        assert_ne!(flipped, mirrored_flipped);
+
+        // Same stones, other side to move
+        mirrored.make_null_move();
+        assert_ne!(mirrored.canonical_key().0, key);
+    }
+
+    #[test]
+    fn test_win_direction() {
+        let cases: [(&[u8], WinDirection); 4] = [
+            (&[0, 0, 1, 1, 2, 2, 3], WinDirection::Horizontal),
+            (&[0, 1, 0, 1, 0, 1, 0], WinDirection::Vertical),
+            (&[0, 1, 1, 2, 2, 3, 2, 3, 3, 6, 3], WinDirection::DiagonalUp),
+            (
+                &[6, 5, 5, 4, 4, 3, 4, 3, 3, 0, 3],
+                WinDirection::DiagonalDown,
+            ),
+        ];
+
+        for (cols, direction) in cases {
+            let mut game = standard_game();
+            let (last, rest) = cols.split_last().expect("test_win_direction: empty case");
+            play_columns(&mut game, rest);
+            assert_eq!(game.win_direction(), None);
+
+            play_columns(&mut game, &[*last]);
+            assert_eq!(game.outcome(), Some(GameOutcome::RedWin));
+            assert_eq!(game.win_direction(), Some(direction));
+
+            game.unmake_move();
+            assert_eq!(game.win_direction(), None);
+        }
+    }
+
+    #[test]
+    fn test_win_direction_not_set_by_resignation() {
+        let mut game = standard_game();
+        game.resign(Player::Red);
+        assert_eq!(game.win_direction(), None);
+    }
+
+    fn cells(cells: &[(u8, u8)]) -> Vec<Position> {
+        cells
+            .iter()
+            .map(|&(col, row)| Position::new(col, row))
+            .collect()
+    }
+
+    #[test]
+    fn test_board_count_matches_moves() {
+        let mut game = standard_game();
+        let cols = [3, 3, 2, 4, 4, 0, 6];
+        for (i, &col) in cols.iter().enumerate() {
+            play_columns(&mut game, &[col]);
+            let plies = i as u32 + 1;
+            assert_eq!(game.board().count(Player::Red), plies.div_ceil(2));
+            assert_eq!(game.board().count(Player::Yellow), plies / 2);
+            assert_eq!(game.board().total_count(), plies);
+        }
+    }
+
+    #[test]
+    fn test_place_stones_legal_setup() {
+        let mut game = standard_game();
+        game.make_move(&Move::new(6, 0));
+
+        let reds = cells(&[(3, 0), (3, 1), (4, 0)]);
+        let yellows = cells(&[(2, 0), (4, 1)]);
+        assert_eq!(game.place_stones(&reds, &yellows), Ok(()));
+
+        assert_eq!(game.turn(), Player::Yellow);
+        assert!(game.move_history().is_empty());
+        assert!(!game.is_over());
+        assert_eq!(game.get_piece(&Position::new(6, 0)), None);
+        assert_eq!(
+            game.get_piece(&Position::new(3, 1)),
+            Some(Player::Red as i8)
+        );
+        assert_eq!(
+            game.get_piece(&Position::new(4, 1)),
+            Some(Player::Yellow as i8)
+        );
+        assert!(game.make_move(&Move::new(3, 2)));
+    }
+
+    #[test]
+    fn test_place_stones_recomputes_outcome() {
+        let mut game = standard_game();
+        let reds = cells(&[(0, 0), (1, 0), (2, 0), (3, 0)]);
+        let yellows = cells(&[(0, 1), (1, 1), (2, 1)]);
+        assert_eq!(game.place_stones(&reds, &yellows), Ok(()));
+
+        assert!(game.is_over());
+        assert_eq!(game.outcome(), Some(GameOutcome::RedWin));
+        assert_eq!(game.win_direction(), Some(WinDirection::Horizontal));
+    }
+
+    #[test]
+    fn test_place_stones_rejects_floating_stone() {
+        let mut game = standard_game();
+        game.make_move(&Move::new(3, 0));
+
+        let result = game.place_stones(&cells(&[(0, 0)]), &cells(&[(2, 1)]));
+        assert_eq!(
+            result,
+            Err(PlacementError::FloatingStone(Position::new(2, 1)))
+        );
+
+        // The game is left untouched
+        assert_eq!(game.move_history(), &[Move::new(3, 0)]);
+        assert_eq!(
+            game.get_piece(&Position::new(3, 0)),
+            Some(Player::Red as i8)
+        );
+        assert_eq!(game.get_piece(&Position::new(0, 0)), None);
+    }
+
+    #[test]
+    fn test_place_stones_rejects_impossible_counts() {
+        let mut game = standard_game();
+        let result = game.place_stones(&cells(&[(0, 0), (1, 0), (2, 0)]), &cells(&[(3, 0)]));
+        assert_eq!(
+            result,
+            Err(PlacementError::InvalidStoneCounts {
+                reds: 3,
+                yellows: 1
+            })
+        );
+
+        // Yellow never gets ahead of Red, who moves first.
+        let result = game.place_stones(&cells(&[(0, 0)]), &cells(&[(1, 0), (2, 0)]));
+        assert_eq!(
+            result,
+            Err(PlacementError::InvalidStoneCounts {
+                reds: 1,
+                yellows: 2
+            })
+        );
+        assert_eq!(
+            game.place_stones(&[], &cells(&[(1, 0)])).err(),
+            Some(PlacementError::InvalidStoneCounts {
+                reds: 0,
+                yellows: 1
+            })
+        );
+    }
+
+    #[test]
+    fn test_place_stones_rejects_bad_positions() {
+        let mut game = standard_game();
+        assert_eq!(
+            game.place_stones(&cells(&[(7, 0)]), &[]),
+            Err(PlacementError::OutOfBounds(Position::new(7, 0)))
+        );
+        assert_eq!(
+            game.place_stones(&cells(&[(0, 0)]), &cells(&[(0, 0)])),
+            Err(PlacementError::DuplicatePosition(Position::new(0, 0)))
+        );
+    }
+
+    #[test]
+    fn test_recheck_terminal_after_set_piece() {
+        let mut game = standard_game();
+        for row in 0..4 {
+            game.set_piece(&Position::new(2, row), Some(Player::Yellow));
+        }
+        assert!(!game.is_over());
+
+        game.recheck_terminal();
+        assert!(game.is_over());
+        assert_eq!(game.outcome(), Some(GameOutcome::YellowWin));
+        assert_eq!(game.win_direction(), Some(WinDirection::Vertical));
+        assert!(game.legal_moves().is_empty());
+
+        // Breaking the line makes the game ongoing again
+        game.set_piece(&Position::new(2, 3), None);
+        game.recheck_terminal();
+        assert!(!game.is_over());
+        assert_eq!(game.outcome(), None);
+        assert_eq!(game.win_direction(), None);
+    }
+
+    #[test]
+    fn test_recheck_terminal_full_board_and_resignation() {
+        let mut game = standard_game();
+        game.resign(Player::Red);
+        game.recheck_terminal();
+        assert_eq!(game.outcome(), Some(GameOutcome::YellowWinByResignation));
+
+        // Fill the board in a pattern with no four in a row
+        let mut game = standard_game();
+        for row in 0..STANDARD_ROWS {
+            for col in 0..STANDARD_COLS {
+                let player = if (col / 2 + row) % 2 == 0 {
+                    Player::Red
+                } else {
+                    Player::Yellow
+                };
+                game.set_piece(&Position::new(col, row), Some(player));
+            }
+        }
+        game.recheck_terminal();
+        assert_eq!(game.outcome(), Some(GameOutcome::Draw));
+    }
+
+    #[test]
+    fn test_null_move_round_trip() {
+        let mut game = standard_game();
+        play_columns(&mut game, &[3, 2, 4]);
+        let before = game.clone();
+
+        game.make_null_move();
+        assert_eq!(game.turn(), Player::Red);
+        assert_eq!(game.board(), before.board());
+        assert_eq!(game.move_history().last(), Some(&Move::NULL));
+
+        game.unmake_null_move();
+        assert_eq!(game.state_hash(), before.state_hash());
+        assert_eq!(game.move_history(), before.move_history());
+    }
+
+    #[test]
+    fn test_unmake_move_undoes_null_move() {
+        let mut game = standard_game();
+        game.make_move(&Move::new(3, 0));
+        game.make_null_move();
+        game.make_move(&Move::new(3, 1));
+        assert_eq!(
+            game.get_piece(&Position::new(3, 1)),
+            Some(Player::Red as i8)
+        );
+
+        assert!(game.unmake_move());
+        assert!(game.unmake_move());
+        assert_eq!(game.turn(), Player::Yellow);
+        assert_eq!(game.move_history(), &[Move::new(3, 0)]);
+    }
+
+    #[test]
+    #[should_panic(expected = "finished game")]
+    fn test_null_move_when_over() {
+        let mut game = standard_game();
+        play_columns(&mut game, &[0, 1, 0, 1, 0, 1, 0]);
+        game.make_null_move();
+    }
+
+    #[test]
+    #[should_panic(expected = "not a null move")]
+    fn test_unmake_null_move_requires_null_move() {
+        let mut game = standard_game();
+        game.make_move(&Move::new(0, 0));
+        game.unmake_null_move();
+    }
+
+    #[test]
+    fn test_resign() {
+        let mut game = standard_game();
+        game.make_move(&Move::new(3, 0));
+
+        assert!(game.resign(Player::Yellow));
+        assert!(game.is_over());
+        assert_eq!(game.outcome(), Some(GameOutcome::RedWinByResignation));
+        assert!(game.legal_moves().is_empty());
+        assert!(!game.make_move(&Move::new(3, 1)));
+
+        // Already over
+        assert!(!game.resign(Player::Red));
+        assert_eq!(game.outcome(), Some(GameOutcome::RedWinByResignation));
+    }
+
+    #[test]
+    fn test_resignation_cannot_be_unmade() {
+        let mut game = standard_game();
+        game.make_move(&Move::new(3, 0));
+        game.resign(Player::Red);
+
+        assert!(!game.unmake_move());
+        assert!(game.is_over());
+        assert_eq!(game.outcome(), Some(GameOutcome::YellowWinByResignation));
+        assert_eq!(game.move_history().len(), 1);
+    }
+
+    #[test]
+    fn test_claim_draw() {
+        let mut game = standard_game();
+        game.make_move(&Move::new(3, 0));
+
+        assert!(game.claim_draw());
+        assert!(game.is_over());
+        assert_eq!(game.outcome(), Some(GameOutcome::ClaimedDraw));
+        assert!(!game.unmake_move());
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_serde_round_trip_resignation() {
+        let mut game = standard_game();
+        game.make_move(&Move::new(3, 0));
+        game.resign(Player::Yellow);
+
+        let json = serde_json::to_string(&game).expect("serialize failed");
+        let restored: StdGame = serde_json::from_str(&json).expect("deserialize failed");
+        assert!(restored.is_over());
+        assert_eq!(restored.outcome(), Some(GameOutcome::RedWinByResignation));
+        assert_eq!(restored.win_direction(), None);
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_serde_round_trip_win_direction() {
+        let mut game = standard_game();
+        play_columns(&mut game, &[0, 1, 1, 2, 2, 3, 2, 3, 3, 6, 3]);
+
+        let json = serde_json::to_string(&game).expect("serialize failed");
+        let restored: StdGame = serde_json::from_str(&json).expect("deserialize failed");
+        assert_eq!(restored.win_direction(), Some(WinDirection::DiagonalUp));
+    }
+
+    #[test]
+    fn test_connect_3() {
+        let mut game = StdGame::with_connect_len(STANDARD_COLS, STANDARD_ROWS, 3);
+        assert_eq!(game.connect_len(), 3);
+
+        // Red wins on its third stone in column 0
+        play_columns(&mut game, &[0, 1, 0, 1]);
+        assert!(!game.is_over());
+        play_columns(&mut game, &[0]);
+        assert!(game.is_over());
+        assert_eq!(game.outcome(), Some(GameOutcome::RedWin));
+        assert_eq!(game.winning_line(), positions(&[(0, 0), (0, 1), (0, 2)]));
+    }
+
+    #[test]
+    fn test_connect_5() {
+        let mut game = StdGame::with_connect_len(STANDARD_COLS, STANDARD_ROWS, 5);
+
+        // Four red stones in row 0 do not win
+        play_columns(&mut game, &[0, 0, 1, 1, 2, 2, 3, 3]);
+        assert!(!game.is_over());
+
+        // The fifth does
+        play_columns(&mut game, &[4]);
+        assert_eq!(game.outcome(), Some(GameOutcome::RedWin));
+        assert_eq!(
+            game.winning_line(),
+            positions(&[(0, 0), (1, 0), (2, 0), (3, 0), (4, 0)])
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_serde_round_trip_connect_len() {
+        let mut game = StdGame::with_connect_len(STANDARD_COLS, STANDARD_ROWS, 3);
+        play_columns(&mut game, &[0, 1, 0, 1, 0]);
+
+        let json = serde_json::to_string(&game).expect("serialize failed");
+        let restored: StdGame = serde_json::from_str(&json).expect("deserialize failed");
+        assert_eq!(restored.connect_len(), 3);
+        assert_eq!(restored.outcome(), Some(GameOutcome::RedWin));
+    }
+
+    #[test]
+    fn test_legal_moves_iter_matches_vec() {
+        use rand::prelude::IndexedRandom;
+        use rand::SeedableRng;
+
+        let mut rng = rand::rngs::SmallRng::seed_from_u64(16);
+
+        for _ in 0..20 {
+            let mut game = standard_game();
+            loop {
+                let moves = game.legal_moves();
+                let iterated: Vec<Move> = game.legal_moves_iter().collect();
+                assert_eq!(iterated, moves);
+                assert_eq!(game.legal_move_count(), moves.len());
+
+                let Some(chosen_move) = moves.choose(&mut rng) else {
+                    break;
+                };
+                game.make_move(chosen_move);
+            }
+            assert!(game.is_over());
+            assert_eq!(game.legal_move_count(), 0);
+        }
+    }
+
+    #[test]
+    fn test_legal_move_count_with_full_column() {
+        let mut game = standard_game();
+        play_columns(&mut game, &[0, 0, 0, 0, 0, 0]);
+
+        assert_eq!(game.legal_move_count(), STANDARD_COLS as usize - 1);
+        assert!(game.legal_moves_iter().all(|m| m.col != 0));
+    }
+
+    #[test]
+    fn test_reset_matches_fresh_game() {
+        let mut game = standard_game();
+        play_columns(&mut game, &[0, 1, 0, 1, 0, 1, 0]);
+        assert!(game.is_over());
+
+        game.reset();
+        let fresh = standard_game();
+        assert_eq!(game.board(), fresh.board());
+        assert_eq!(game.turn(), fresh.turn());
+        assert_eq!(game.legal_moves(), fresh.legal_moves());
+        assert!(!game.is_over());
+        assert_eq!(game.outcome(), None);
+        assert_eq!(game.win_direction(), None);
+        assert!(game.move_history().is_empty());
+        assert_eq!(game.state_hash(), fresh.state_hash());
+
+        play_columns(&mut game, &[3]);
+        assert_eq!(
+            game.get_piece(&Position::new(3, 0)),
+            Some(Player::Red as i8)
+        );
+    }
+
+    #[test]
+    fn test_children() {
+        let mut game = standard_game();
+        play_columns(&mut game, &[3, 3, 3, 3, 3, 3]);
+        let before = game.state_hash();
+
+        let children: Vec<_> = game.children().collect();
+        assert_eq!(children.len(), game.legal_moves().len());
+        assert_eq!(children.len(), STANDARD_COLS as usize - 1);
+        for (move_, child) in &children {
+            assert_eq!(child.move_history().len(), game.move_history().len() + 1);
+            assert_eq!(child.move_history().last(), Some(move_));
+            assert_eq!(child.turn(), game.turn().opposite());
+        }
+        assert_eq!(game.state_hash(), before);
+
+        play_columns(&mut game, &[0, 1, 0, 1, 0, 1, 0]);
+        assert!(game.is_over());
+        assert_eq!(game.children().count(), 0);
+    }
+
+    #[test]
+    fn test_legal_moves_ordered() {
+        let mut game = standard_game();
+        let cols = |moves: Vec<Move>| moves.iter().map(|m| m.col).collect::<Vec<_>>();
+        assert_eq!(cols(game.legal_moves_ordered()), vec![3, 2, 4, 1, 5, 0, 6]);
+        assert_eq!(cols(game.legal_moves()), vec![0, 1, 2, 3, 4, 5, 6]);
+
+        play_columns(&mut game, &[2, 2, 2, 2, 3, 2, 2]);
+        assert_eq!(cols(game.legal_moves_ordered()), vec![3, 4, 1, 5, 0, 6]);
+        for move_ in game.legal_moves_ordered() {
+            assert!(game.is_legal_move(&move_));
+        }
+
+        type EvenGame = Game<{ nw_for_board(6, 6) }>;
+        let even = EvenGame::new(6, 6);
+        assert_eq!(cols(even.legal_moves_ordered()), vec![2, 3, 1, 4, 0, 5]);
+    }
+
+    /// Place stones from a diagram with the top row first, `R`/`Y` for stones
+    /// and `.` for empty cells.
+    fn game_from_diagram(rows: &[&str]) -> StdGame {
+        let (mut reds, mut yellows) = (Vec::new(), Vec::new());
+        for (i, line) in rows.iter().enumerate() {
+            let row = (rows.len() - 1 - i) as u8;
+            for (col, ch) in line.chars().enumerate() {
+                let pos = Position::new(col as u8, row);
+                match ch {
+                    'R' => reds.push(pos),
+                    'Y' => yellows.push(pos),
+                    _ => {}
+                }
+            }
+        }
+        let mut game = standard_game();
+        game.place_stones(&reds, &yellows)
+            .expect("game_from_diagram: diagram must be a valid position");
+        game
+    }
+
+    #[test]
+    fn test_is_dead_draw() {
+        let mut game = game_from_diagram(&[
+            "Y.RYY.R", //
+            "RYRYRYR", //
+            "RYRRRYR", //
+            "RRYYYRY", //
+            "YRYRYYY", //
+            "RYYRYRR",
+        ]);
+        assert!(!game.is_over());
+        assert!(game.is_dead_draw());
+
+        play_columns(&mut game, &[1]);
+        assert!(!game.is_over());
+        assert!(game.is_dead_draw());
+        play_columns(&mut game, &[5]);
+        assert_eq!(game.outcome(), Some(GameOutcome::Draw));
+        assert!(game.is_dead_draw());
+    }
+
+    #[test]
+    fn test_is_dead_draw_with_room_left() {
+        assert!(!standard_game().is_dead_draw());
+
+        // Emptying the top-left cell leaves Red room for four in column 0.
+        let game = game_from_diagram(&[
+            "..RYY.R", //
+            "RYRYRYR", //
+            "RYRRRYR", //
+            "RRYYYRY", //
+            "YRYRYYY", //
+            "RYYRYRR",
+        ]);
+        assert!(!game.is_dead_draw());
+    }
+
+    #[test]
+    fn test_to_pretty_string() {
+        let mut game = standard_game();
+        assert!(game.to_pretty_string().ends_with("Moves:\n"));
+
+        play_columns(&mut game, &[3, 3, 2]);
+        game.make_null_move();
+        play_columns(&mut game, &[6]);
+
+        let pretty = game.to_pretty_string();
+        assert!(pretty.starts_with(&game.to_string()));
+        let history = pretty
+            .split_once("Moves:\n")
+            .expect("test_to_pretty_string: history section missing")
+            .1;
+        assert_eq!(history, "1. 3 3\n2. 2 -\n3. 6\n");
+    }
+
+    #[test]
+    fn test_same_position() {
+        let mut a = standard_game();
+        let mut b = standard_game();
+        play_columns(&mut a, &[3, 2, 4, 5]);
+        play_columns(&mut b, &[4, 5, 3, 2]);
+
+        assert_ne!(a.move_history(), b.move_history());
+        assert!(a.same_position(&b));
+        assert!(b.same_position(&a));
+
+        play_columns(&mut a, &[0]);
+        assert!(!a.same_position(&b));
+        play_columns(&mut b, &[0]);
+        assert!(a.same_position(&b));
+
+        // Same stones, different side to move.
+        let mut c = b.clone();
+        c.make_null_move();
+        assert!(!c.same_position(&b));
+
+        // A resignation ends the game without touching the board.
+        let mut d = b.clone();
+        d.resign(Player::Yellow);
+        assert!(d.same_position(&b));
+    }
+
+    #[test]
+    fn test_last_move() {
+        let mut game = standard_game();
+        assert_eq!(game.last_move(), None);
+
+        play_columns(&mut game, &[3]);
+        assert_eq!(game.last_move(), Some(Move::new(3, 0)));
+        play_columns(&mut game, &[3]);
+        assert_eq!(game.last_move(), Some(Move::new(3, 1)));
+
+        assert!(game.unmake_move());
+        assert_eq!(game.last_move(), Some(Move::new(3, 0)));
+        assert!(game.unmake_move());
+        assert_eq!(game.last_move(), None);
+    }
+
+    #[test]
+    fn test_unmake_moves() {
+        let mut game = standard_game();
+        play_columns(&mut game, &[3, 3, 2]);
+        let after_one = game.state_hash();
+        play_columns(&mut game, &[2, 4, 5, 1]);
+        assert!(game.is_over());
+
+        assert_eq!(game.unmake_moves(0), 0);
+        assert_eq!(game.ply(), 7);
+
+        assert_eq!(game.unmake_moves(4), 4);
+        assert_eq!(game.state_hash(), after_one);
+        assert!(!game.is_over());
+        assert_eq!(game.outcome(), None);
+        assert_eq!(game.turn(), Player::Yellow);
+
+        assert_eq!(game.unmake_moves(10), 3);
+        assert_eq!(game.state_hash(), standard_game().state_hash());
+        assert_eq!(game.unmake_moves(1), 0);
+    }
+
+    #[test]
+    fn test_winner() {
+        let mut game = standard_game();
+        assert_eq!(game.winner(), None);
+        play_columns(&mut game, &[0, 1, 0, 1, 0, 1]);
+        assert_eq!(game.winner(), None);
+        play_columns(&mut game, &[0]);
+        assert_eq!(game.winner(), Some(Player::Red));
+
+        type SmallGame = Game<{ nw_for_board(4, 4) }>;
+        let mut drawn = SmallGame::new(4, 4);
+        play_columns(
+            &mut drawn,
+            &[1, 1, 2, 3, 0, 0, 3, 2, 1, 1, 3, 2, 3, 2, 0, 0],
+        );
+        assert_eq!(drawn.outcome(), Some(GameOutcome::Draw));
+        assert_eq!(drawn.winner(), None);
+
+        let mut resigned = standard_game();
+        resigned.resign(Player::Red);
+        assert_eq!(resigned.winner(), Some(Player::Yellow));
+    }
+
+    #[test]
+    #[cfg(feature = "rand")]
+    fn test_random_playout_terminates() {
+        use rand::SeedableRng;
+
+        let mut rng = rand::rngs::SmallRng::seed_from_u64(0x5eed);
+        let mut game = standard_game();
+        play_columns(&mut game, &[3, 3]);
+        let before = game.state_hash();
+
+        let mut decided = 0;
+        for _ in 0..500 {
+            let outcome = game.random_playout(&mut rng);
+            assert!(outcome.is_decided_on_board());
+            decided += outcome.winner().is_some() as u32;
+        }
+        assert!(decided > 0);
+        assert_eq!(game.state_hash(), before);
+
+        play_columns(&mut game, &[0, 1, 0, 1, 0, 1, 0]);
+        assert_eq!(game.random_playout(&mut rng), GameOutcome::RedWin);
+    }
+
+    #[test]
+    fn test_make_moves() {
+        let moves = |cols: &[(u8, u8)]| -> Vec<Move> {
+            cols.iter().map(|&(col, row)| Move::new(col, row)).collect()
+        };
+
+        let mut game = standard_game();
+        let opening = moves(&[(3, 0), (3, 1), (2, 0)]);
+        assert_eq!(game.make_moves(&opening), 3);
+        assert_eq!(game.move_history(), opening.as_slice());
+
+        // Red completes column 0 on the seventh ply; the rest are rejected.
+        let mut game = standard_game();
+        let sequence = moves(&[
+            (0, 0),
+            (1, 0),
+            (0, 1),
+            (1, 1),
+            (0, 2),
+            (1, 2),
+            (0, 3),
+            (1, 3),
+            (2, 0),
+        ]);
+        assert_eq!(game.make_moves(&sequence), 7);
+        assert_eq!(game.outcome(), Some(GameOutcome::RedWin));
+        assert_eq!(game.ply(), 7);
+
+        // Column 9 does not exist on a 7-wide board.
+        let mut game = standard_game();
+        let sequence = moves(&[(3, 0), (9, 0), (3, 1)]);
+        assert_eq!(game.make_moves(&sequence), 1);
+        assert_eq!(game.last_move(), Some(Move::new(3, 0)));
+        assert_eq!(game.turn(), Player::Yellow);
+    }
+
+    #[test]
+    fn test_move_threat_counts() {
+        let mut game = standard_game();
+        play_columns(&mut game, &[2, 2, 3, 3]);
+
+        let counts = game.move_threat_counts();
+        assert_eq!(counts.len(), game.legal_move_count());
+        let count_for = |col: u8| {
+            counts
+                .iter()
+                .find(|(move_, _)| move_.col == col)
+                .map(|&(_, count)| count)
+                .expect("test_move_threat_counts: column must be legal")
+        };
+        // Extending the bottom-row pair to an open three threatens both ends.
+        assert!(count_for(4) >= 2);
+        assert!(count_for(1) >= 2);
+        assert_eq!(count_for(6), 0);
+
+        let before = game.state_hash();
+        game.move_threat_counts();
+        assert_eq!(game.state_hash(), before);
+    }
+
+    #[test]
+    fn test_undo_to_ply() {
+        let mut game = standard_game();
+        play_columns(&mut game, &[3, 3, 2]);
+        let at_three = game.state_hash();
+        play_columns(&mut game, &[2, 4, 5, 1]);
+        assert!(game.is_over());
+
+        assert!(!game.undo_to_ply(8));
+        assert_eq!(game.ply(), 7);
+
+        assert!(game.undo_to_ply(7));
+        assert_eq!(game.ply(), 7);
+
+        assert!(game.undo_to_ply(3));
+        assert_eq!(game.state_hash(), at_three);
+        assert!(!game.is_over());
+        assert_eq!(game.outcome(), None);
+        assert_eq!(game.turn(), Player::Yellow);
+
+        assert!(game.undo_to_ply(0));
+        let fresh = standard_game();
+        assert_eq!(game.board(), fresh.board());
+        assert_eq!(game.turn(), fresh.turn());
+        assert_eq!(game.state_hash(), fresh.state_hash());
+
+        play_columns(&mut game, &[3, 3]);
+        game.resign(Player::Red);
+        assert!(!game.undo_to_ply(0));
+        assert_eq!(game.ply(), 2);
+    }
+
+    #[test]
+    fn test_terminal_value() {
+        let mut game = standard_game();
+        assert_eq!(game.terminal_value(), None);
+        play_columns(&mut game, &[0, 1, 0, 1, 0, 1, 0]);
+        assert_eq!(game.terminal_value(), Some(1.0));
+
+        type SmallGame = Game<{ nw_for_board(4, 4) }>;
+        let mut drawn = SmallGame::new(4, 4);
+        play_columns(&mut drawn, &[1, 1, 2, 3, 0, 0, 3, 2, 1, 1, 3, 2, 3, 2]);
+        assert_eq!(drawn.terminal_value(), None);
+        play_columns(&mut drawn, &[0, 0]);
+        assert_eq!(drawn.terminal_value(), Some(0.0));
+    }
+
+    #[test]
+    fn test_to_grid_matches_get_piece() {
+        let mut game = Game::<{ nw_for_board(9, 9) }>::new(9, 9);
+        play_columns(&mut game, &[4, 4, 3, 8, 4, 0, 8, 8]);
+
+        let grid = game.to_grid();
+        assert_eq!(grid.len(), 9);
+        for (row, cells) in grid.iter().enumerate() {
+            assert_eq!(cells.len(), 9);
+            for (col, &cell) in cells.iter().enumerate() {
+                let pos = Position::new(col as u8, row as u8);
+                assert_eq!(cell.map(|p| p as i8), game.get_piece(&pos));
+            }
+        }
+        assert_eq!(grid[0][4], Some(Player::Red));
+        assert_eq!(grid[2][8], Some(Player::Yellow));
+    }
+
+    #[test]
+    fn test_cached_legal_moves_tracks_mutations() {
+        fn assert_fresh<const NW: usize>(game: &Game<NW>) {
+            let fresh: Vec<Move> = game.legal_moves_iter().collect();
+            // A clone starts with an empty cache, exercising the uncached path.
+            assert_eq!(game.clone().legal_moves(), fresh);
+            // Ask twice so the second answer comes from the cache.
+            assert_eq!(game.cached_legal_moves(), fresh.as_slice());
+            assert_eq!(game.cached_legal_moves(), fresh.as_slice());
+            assert_eq!(game.legal_moves(), fresh);
+        }
+
+        let mut game = standard_game();
+        assert_fresh(&game);
+
+        play_columns(&mut game, &[3, 3, 0]);
+        assert_fresh(&game);
+
+        game.unmake_move();
+        assert_fresh(&game);
+
+        for row in 2..6 {
+            game.set_piece(&Position::new(3, row), Some(Player::Red));
+            assert_fresh(&game);
+        }
+        assert!(game.cached_legal_moves().iter().all(|m| m.col != 3));
+
+        game.recheck_terminal();
+        assert!(game.is_over());
+        assert_fresh(&game);
+
+        game.reset();
+        assert_fresh(&game);
+
+        game.make_null_move();
+        assert_fresh(&game);
+        game.unmake_null_move();
+
+        game.place_stones(&[Position::new(0, 0)], &[])
+            .expect("test_cached_legal_moves_tracks_mutations: valid placement");
+        assert_fresh(&game);
+
+        let clone = game.clone();
+        game.resign(Player::Red);
+        assert_fresh(&game);
+        assert!(game.cached_legal_moves().is_empty());
+        assert_fresh(&clone);
+
+        let mut drawn = clone;
+        drawn.claim_draw();
+        assert_fresh(&drawn);
+    }
+
+    #[test]
+    fn test_try_move_errors() {
+        let mut game = standard_game();
+        assert_eq!(game.try_move(&Move::new(3, 0)), Ok(()));
+        assert_eq!(game.move_history(), &[Move::new(3, 0)]);
+
+        assert_eq!(
+            game.try_move(&Move::new(STANDARD_COLS, 0)),
+            Err(MoveError::ColumnOutOfRange(STANDARD_COLS))
+        );
+        assert_eq!(
+            game.try_move(&Move::new(3, 0)),
+            Err(MoveError::WrongRow {
+                col: 3,
+                row: 0,
+                landing_row: 1
+            })
+        );
+
+        play_columns(&mut game, &[3, 3, 3, 3, 3]);
+        assert_eq!(
+            game.try_move(&Move::new(3, STANDARD_ROWS)),
+            Err(MoveError::ColumnFull(3))
+        );
+        assert_eq!(game.ply(), 6);
+
+        play_columns(&mut game, &[0, 1, 0, 1, 0, 1, 0]);
+        assert!(game.is_over());
+        assert_eq!(game.try_move(&Move::new(2, 0)), Err(MoveError::GameOver));
+        assert_eq!(game.ply(), 13);
+    }
+
+    #[test]
+    fn test_try_drop() {
+        let mut game = standard_game();
+        assert_eq!(game.try_drop(3), Ok(Move::new(3, 0)));
+        assert_eq!(game.try_drop(3), Ok(Move::new(3, 1)));
+        assert_eq!(
+            game.try_drop(STANDARD_COLS),
+            Err(MoveError::ColumnOutOfRange(STANDARD_COLS))
+        );
+
+        play_columns(&mut game, &[3, 3, 3, 3]);
+        assert_eq!(game.try_drop(3), Err(MoveError::ColumnFull(3)));
+        assert_eq!(game.ply(), 6);
+
+        play_columns(&mut game, &[0, 1, 0, 1, 0, 1, 0]);
+        assert_eq!(game.try_drop(2), Err(MoveError::GameOver));
+        assert_eq!(game.ply(), 13);
+    }
+
+    #[test]
+    fn test_outcome_after() {
+        let mut game = standard_game();
+        play_columns(&mut game, &[0, 1, 0, 1, 0, 1]);
+        let before = game.state_hash();
+
+        assert_eq!(
+            game.outcome_after(&Move::new(0, 3)),
+            Some(Some(GameOutcome::RedWin))
+        );
+        assert_eq!(game.outcome_after(&Move::new(4, 0)), Some(None));
+        assert_eq!(game.outcome_after(&Move::new(4, 2)), None);
+        assert_eq!(game.outcome_after(&Move::new(STANDARD_COLS, 0)), None);
+        assert_eq!(game.state_hash(), before);
+
+        type SmallGame = Game<{ nw_for_board(4, 4) }>;
+        let mut drawn = SmallGame::new(4, 4);
+        play_columns(&mut drawn, &[1, 1, 2, 3, 0, 0, 3, 2, 1, 1, 3, 2, 3, 2, 0]);
+        assert_eq!(
+            drawn.outcome_after(&Move::new(0, 3)),
+            Some(Some(GameOutcome::Draw))
+        );
+    }
+
+    #[test]
+    fn test_eval_features() {
+        assert_eq!(standard_game().eval_features(), EvalFeatures::default());
+
+        // Red's three on the bottom row is blocked on the right, leaving a
+        // single threat at (0, 0); Yellow has none.
+        let game = game_from_diagram(&[
+            ".RRRY.Y", //
+        ]);
+        assert_eq!(
+            game.eval_features(),
+            EvalFeatures {
+                red_threats: 1,
+                yellow_threats: 0,
+                red_stones: 3,
+                yellow_stones: 2,
+                center_control: 1,
+            }
+        );
+        assert!(game.threat_cells(Player::Red).get(0));
+    }
+
+    #[test]
+    fn test_record_round_trip() {
+        let mut game = StdGame::with_connect_len(STANDARD_COLS, STANDARD_ROWS, 3);
+        for col in [3, 3] {
+            game.make_move(&Move::new(col, game.board().column_height(col, game.geo())));
+        }
+        game.make_null_move();
+        game.make_move(&Move::new(4, 0));
+
+        let record = game.to_record();
+        assert_eq!(record.connect_len, 3);
+        assert_eq!(record.moves, vec![Some(3), Some(3), None, Some(4)]);
+
+        // The null move keeps Yellow's stone in column 4, as in the original.
+        let replayed = StdGame::from_record(&record).expect("test_record_round_trip: valid record");
+        assert!(replayed.same_position(&game));
+        assert_eq!(replayed.move_history(), game.move_history());
+        assert_eq!(replayed.connect_len(), 3);
+        assert_eq!(
+            replayed.board().get_piece(&Position::new(4, 0)),
+            Some(Player::Yellow)
+        );
+    }
+
+    #[test]
+    fn test_record_replays_connect_len() {
+        // Three in a row wins Connect-3 but not Connect-4.
+        let mut game = StdGame::with_connect_len(STANDARD_COLS, STANDARD_ROWS, 3);
+        for col in [0, 6, 1, 6, 2] {
+            game.make_move(&Move::new(col, game.board().column_height(col, game.geo())));
+        }
+        assert_eq!(game.winner(), Some(Player::Red));
+
+        let replayed = StdGame::from_record(&game.to_record())
+            .expect("test_record_replays_connect_len: valid record");
+        assert_eq!(replayed.outcome(), game.outcome());
+    }
+
+    #[test]
+    fn test_from_record_rejects_invalid() {
+        let record = |width, height, connect_len, moves: &[Option<u8>]| GameRecord {
+            width,
+            height,
+            connect_len,
+            moves: moves.to_vec(),
+            outcome: None,
+        };
+
+        assert_eq!(
+            StdGame::from_record(&record(40, 6, 4, &[])).err(),
+            Some(RecordError::Rules(BuildError::InvalidDimensions {
+                width: 40,
+                height: 6
+            }))
+        );
+        assert_eq!(
+            StdGame::from_record(&record(9, 9, 4, &[])).err(),
+            Some(RecordError::Rules(BuildError::WordCountMismatch {
+                needed: 2,
+                nw: 1
+            }))
+        );
+        assert_eq!(
+            StdGame::from_record(&record(7, 6, 8, &[])).err(),
+            Some(RecordError::Rules(BuildError::InvalidConnectLen {
+                connect_len: 8,
+                max: 7
+            }))
+        );
+        assert_eq!(
+            StdGame::from_record(&record(7, 6, 4, &[Some(3), Some(7)])).err(),
+            Some(RecordError::Move(MoveStringError::ColumnOutOfRange {
+                ply: 1,
+                col: 7
+            }))
+        );
+        assert_eq!(
+            StdGame::from_record(&record(7, 6, 4, &[Some(0); 7])).err(),
+            Some(RecordError::Move(MoveStringError::ColumnFull {
+                ply: 6,
+                col: 0
+            }))
+        );
+        let won: Vec<_> = [0, 1, 0, 1, 0, 1, 0, 1].map(Some).to_vec();
+        assert_eq!(
+            StdGame::from_record(&record(7, 6, 4, &won)).err(),
+            Some(RecordError::Move(MoveStringError::GameOver { ply: 7 }))
+        );
+    }
+
+    #[test]
+    fn test_game_builder() {
+        let game: Game<{ nw_for_board(9, 7) }> = GameBuilder::new()
+            .width(9)
+            .height(7)
+            .connect_len(5)
+            .gravity(true)
+            .build()
+            .expect("test_game_builder: valid rules");
+        assert_eq!((game.width(), game.height(), game.connect_len()), (9, 7, 5));
+        assert_eq!(game.legal_moves().len(), 9);
+
+        let standard: StdGame = GameBuilder::new()
+            .build()
+            .expect("test_game_builder: standard rules");
+        assert!(standard.same_position(&StdGame::new(STANDARD_COLS, STANDARD_ROWS)));
+        assert_eq!(standard.connect_len(), DEFAULT_CONNECT_LEN);
+    }
+
+    #[test]
+    fn test_game_builder_rejects_invalid_rules() {
+        let builder = GameBuilder::new().width(5).height(4);
+        assert_eq!(
+            builder.connect_len(6).build::<1>().err(),
+            Some(BuildError::InvalidConnectLen {
+                connect_len: 6,
+                max: 5
+            })
+        );
+        assert!(builder.connect_len(5).build::<1>().is_ok());
+        assert_eq!(
+            builder.connect_len(1).build::<1>().err(),
+            Some(BuildError::InvalidConnectLen {
+                connect_len: 1,
+                max: 5
+            })
+        );
+        assert_eq!(
+            builder.gravity(false).build::<1>().err(),
+            Some(BuildError::GravityRequired)
+        );
+        assert_eq!(
+            builder.width(3).build::<1>().err(),
+            Some(BuildError::InvalidDimensions {
+                width: 3,
+                height: 4
+            })
+        );
+        assert_eq!(
+            builder.build::<2>().err(),
+            Some(BuildError::WordCountMismatch { needed: 1, nw: 2 })
+        );
+    }
 }
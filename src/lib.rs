@@ -1,12 +1,20 @@
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
 pub mod bitboard;
 pub mod board;
 pub mod encode;
 pub mod game;
 mod limits;
+#[cfg(feature = "log")]
+pub mod log;
 pub mod r#move;
 pub mod outcome;
 pub mod player;
 pub mod position;
+pub mod solve;
+pub mod zobrist;
 
 #[cfg(feature = "python")]
 extern crate pyo3;
@@ -27,6 +35,9 @@ fn spooky_connect4(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<PyMove>()?;
     m.add_class::<PyGameOutcome>()?;
     m.add_function(wrap_pyfunction!(augment_symmetries, m)?)?;
+    m.add_function(wrap_pyfunction!(step_many, m)?)?;
+    m.add_function(wrap_pyfunction!(legal_action_masks, m)?)?;
+    m.add_function(wrap_pyfunction!(py_nw_for_board, m)?)?;
     m.add("RED", Player::Red as i8)?;
     m.add("YELLOW", Player::Yellow as i8)?;
     m.add("TOTAL_INPUT_PLANES", encode::TOTAL_INPUT_PLANES)?;
@@ -1,6 +1,26 @@
+//! Supported board sizes.
+//!
+//! Boards are at most 32×32. Per-column tables (`BoardGeometry::column_masks`,
+//! `Board`'s column heights, `Game::legal_action_mask`) are sized by
+//! `MAX_BOARD_DIMENSION`, and the Python bindings instantiate bitboards up to
+//! `nw_for_board(32, 32) = 16` words. Larger sizes are rejected when a board or
+//! geometry is constructed rather than producing corrupt masks.
+
+use crate::bitboard::nw_for_board;
+
 pub const MIN_BOARD_DIMENSION: u8 = 4;
 pub const MAX_BOARD_DIMENSION: u8 = 32;
 
+/// Largest number of bitboard words any supported board needs.
+pub const MAX_NW: usize = nw_for_board(MAX_BOARD_DIMENSION, MAX_BOARD_DIMENSION);
+
+// The cell count is stored as `u16` (`BoardGeometry::area`).
+const _: () =
+    assert!((MAX_BOARD_DIMENSION as u32) * (MAX_BOARD_DIMENSION as u32) <= u16::MAX as u32);
+// The Python bindings dispatch over 1..=16 words, so every board must fit.
+const _: () = assert!((MAX_BOARD_DIMENSION as usize).pow(2) <= 16 * 64);
+const _: () = assert!(MAX_NW == 16);
+
 #[inline]
 pub const fn board_dimension_is_valid(dimension: u8) -> bool {
     dimension >= MIN_BOARD_DIMENSION && dimension <= MAX_BOARD_DIMENSION
@@ -0,0 +1,67 @@
+//! Append-only JSONL logs of finished games, for training data.
+
+use std::fs::OpenOptions;
+use std::io::{self, Write};
+use std::path::Path;
+
+use crate::game::GameRecord;
+
+/// Append `record` to the file at `path` as one line of JSON, creating the
+/// file if it does not exist.
+pub fn append_jsonl(path: impl AsRef<Path>, record: &GameRecord) -> io::Result<()> {
+    let line = serde_json::to_string(record).map_err(io::Error::other)?;
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{}", line)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bitboard::nw_for_board;
+    use crate::game::Game;
+    use crate::r#move::Move;
+
+    type StdGame = Game<{ nw_for_board(7, 6) }>;
+
+    #[test]
+    fn test_append_jsonl_round_trip() {
+        let path =
+            std::env::temp_dir().join(format!("spooky_connect4_log_{}.jsonl", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+
+        let won = StdGame::from_move_string(7, 6, "4545454")
+            .expect("test_append_jsonl_round_trip: valid game");
+        let mut resigned = StdGame::from_move_string(7, 6, "17")
+            .expect("test_append_jsonl_round_trip: valid game");
+        resigned.resign(resigned.turn());
+        let mut connect5 = StdGame::with_connect_len(7, 6, 5);
+        connect5.make_move(&Move::new(3, 0));
+        connect5.make_null_move();
+        connect5.make_move(&Move::new(3, 1));
+        let games = [won, resigned, connect5];
+
+        for game in &games {
+            append_jsonl(&path, &game.to_record())
+                .expect("test_append_jsonl_round_trip: append failed");
+        }
+
+        let contents =
+            std::fs::read_to_string(&path).expect("test_append_jsonl_round_trip: read failed");
+        std::fs::remove_file(&path).expect("test_append_jsonl_round_trip: cleanup failed");
+        let records: Vec<GameRecord> = contents
+            .lines()
+            .map(|line| serde_json::from_str(line).expect("test_append_jsonl_round_trip: bad line"))
+            .collect();
+        assert_eq!(records.len(), games.len());
+
+        for (record, game) in records.iter().zip(&games) {
+            assert_eq!(record, &game.to_record());
+            let replayed =
+                StdGame::from_record(record).expect("test_append_jsonl_round_trip: record replays");
+            assert!(replayed.same_position(game));
+            assert_eq!(replayed.move_history(), game.move_history());
+        }
+        assert_eq!(records[0].outcome, games[0].outcome());
+        assert_eq!(records[1].outcome, games[1].outcome());
+    }
+}
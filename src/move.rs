@@ -1,23 +1,238 @@
+use alloc::string::{String, ToString};
+
+use crate::game::Game;
 use crate::position::Position;
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+/// Error returned by `Move::from_column_str`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ParseMoveError {
+    /// The input is neither a column number nor a column letter.
+    InvalidFormat(String),
+    /// The column does not exist on this board.
+    ColumnOutOfRange { col: usize, width: u8 },
+    /// The column exists but has no empty cell.
+    ColumnFull(u8),
+}
+
+impl core::fmt::Display for ParseMoveError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            ParseMoveError::InvalidFormat(s) => write!(f, "Invalid column '{}'", s),
+            ParseMoveError::ColumnOutOfRange { col, width } => write!(
+                f,
+                "Column {} is out of range for a board of width {}",
+                col, width
+            ),
+            ParseMoveError::ColumnFull(col) => write!(f, "Column {} is full", col),
+        }
+    }
+}
+
+impl core::error::Error for ParseMoveError {}
+
+/// Moves order by column, then by row, so `Move::NULL` sorts after every
+/// real move.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Move {
     pub col: u8,
     pub row: u8,
 }
 
 impl Move {
+    /// Marker recorded in the move history by `Game::make_null_move`.
+    /// It does not refer to a cell on any board.
+    pub const NULL: Move = Move {
+        col: u8::MAX,
+        row: u8::MAX,
+    };
+
     pub fn new(col: u8, row: u8) -> Self {
         Move { col, row }
     }
 
+    pub fn is_null(&self) -> bool {
+        *self == Move::NULL
+    }
+
+    /// Parse a column as a 0-based number (`"3"`) or a letter (`"d"`, where
+    /// `a` is column 0), inferring the landing row from the current board.
+    pub fn from_column_str<const NW: usize>(
+        s: &str,
+        game: &Game<NW>,
+    ) -> Result<Move, ParseMoveError> {
+        let s = s.trim();
+        let mut chars = s.chars();
+        let col = match (chars.next(), chars.next()) {
+            (Some(c), None) if c.is_ascii_alphabetic() => {
+                (c.to_ascii_lowercase() as u8 - b'a') as usize
+            }
+            _ => s
+                .parse::<usize>()
+                .map_err(|_| ParseMoveError::InvalidFormat(s.to_string()))?,
+        };
+
+        let width = game.width();
+        if col >= width as usize {
+            return Err(ParseMoveError::ColumnOutOfRange { col, width });
+        }
+
+        let col = col as u8;
+        if game.board().is_column_full(col) {
+            return Err(ParseMoveError::ColumnFull(col));
+        }
+
+        Ok(Move::new(col, game.board().column_height(col, game.geo())))
+    }
+
+    /// Whether the move's cell lies on `game`'s board. Unlike
+    /// `Game::is_legal_move`, this ignores gravity and whether the game is
+    /// over.
+    pub fn is_valid_for<const NW: usize>(&self, game: &Game<NW>) -> bool {
+        self.col < game.width() && self.row < game.height()
+    }
+
     pub fn position(&self) -> Position {
         Position::new(self.col, self.row)
     }
+
+    /// The move reflected left-to-right on a board of the given width. A
+    /// null move, or any move whose column is off the board, has no mirror
+    /// image and is returned unchanged.
+    pub fn mirror_horizontal(&self, width: u8) -> Move {
+        if self.col >= width {
+            return *self;
+        }
+        Move::new(width - 1 - self.col, self.row)
+    }
 }
 
-impl std::fmt::Display for Move {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Display for Move {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         write!(f, "Move(col: {}, row: {})", self.col, self.row)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bitboard::nw_for_board;
+    use crate::board::{STANDARD_COLS, STANDARD_ROWS};
+
+    type StdGame = Game<{ nw_for_board(STANDARD_COLS, STANDARD_ROWS) }>;
+
+    #[test]
+    fn test_from_column_str_numeric() {
+        let mut game = StdGame::new(STANDARD_COLS, STANDARD_ROWS);
+        assert_eq!(Move::from_column_str("3", &game), Ok(Move::new(3, 0)));
+        assert_eq!(Move::from_column_str(" 0 ", &game), Ok(Move::new(0, 0)));
+
+        game.make_move(&Move::new(3, 0));
+        assert_eq!(Move::from_column_str("3", &game), Ok(Move::new(3, 1)));
+    }
+
+    #[test]
+    fn test_from_column_str_alphabetic() {
+        let game = StdGame::new(STANDARD_COLS, STANDARD_ROWS);
+        assert_eq!(Move::from_column_str("a", &game), Ok(Move::new(0, 0)));
+        assert_eq!(Move::from_column_str("d", &game), Ok(Move::new(3, 0)));
+        assert_eq!(Move::from_column_str("G", &game), Ok(Move::new(6, 0)));
+    }
+
+    #[test]
+    fn test_from_column_str_invalid() {
+        let mut game = StdGame::new(STANDARD_COLS, STANDARD_ROWS);
+
+        assert_eq!(
+            Move::from_column_str("7", &game),
+            Err(ParseMoveError::ColumnOutOfRange { col: 7, width: 7 })
+        );
+        assert_eq!(
+            Move::from_column_str("h", &game),
+            Err(ParseMoveError::ColumnOutOfRange { col: 7, width: 7 })
+        );
+        assert_eq!(
+            Move::from_column_str("", &game),
+            Err(ParseMoveError::InvalidFormat(String::new()))
+        );
+        assert_eq!(
+            Move::from_column_str("-1", &game),
+            Err(ParseMoveError::InvalidFormat("-1".to_string()))
+        );
+        assert_eq!(
+            Move::from_column_str("d4", &game),
+            Err(ParseMoveError::InvalidFormat("d4".to_string()))
+        );
+
+        for row in 0..STANDARD_ROWS {
+            game.make_move(&Move::new(0, row));
+        }
+        let err = Move::from_column_str("a", &game);
+        assert_eq!(err, Err(ParseMoveError::ColumnFull(0)));
+        assert_eq!(
+            err.map_err(|e| e.to_string()),
+            Err("Column 0 is full".to_string())
+        );
+    }
+
+    #[test]
+    fn test_is_valid_for() {
+        let game = StdGame::new(STANDARD_COLS, STANDARD_ROWS);
+
+        // On the board but floating above an empty column.
+        let floating = Move::new(3, 2);
+        assert!(floating.is_valid_for(&game));
+        assert!(!game.is_legal_move(&floating));
+
+        let landing = Move::new(6, 0);
+        assert!(landing.is_valid_for(&game));
+        assert!(game.is_legal_move(&landing));
+
+        assert!(!Move::new(STANDARD_COLS, 0).is_valid_for(&game));
+        assert!(!Move::new(0, STANDARD_ROWS).is_valid_for(&game));
+        assert!(!Move::NULL.is_valid_for(&game));
+    }
+
+    #[test]
+    fn test_move_ordering() {
+        let mut moves = vec![
+            Move::new(3, 0),
+            Move::NULL,
+            Move::new(0, 2),
+            Move::new(3, 1),
+            Move::new(0, 0),
+            Move::new(6, 0),
+            Move::new(1, 5),
+        ];
+        moves.sort();
+        assert_eq!(
+            moves,
+            vec![
+                Move::new(0, 0),
+                Move::new(0, 2),
+                Move::new(1, 5),
+                Move::new(3, 0),
+                Move::new(3, 1),
+                Move::new(6, 0),
+                Move::NULL,
+            ]
+        );
+
+        let set: std::collections::BTreeSet<Move> =
+            [Move::new(2, 1), Move::new(2, 0), Move::new(2, 1)].into();
+        assert_eq!(
+            set.into_iter().collect::<Vec<_>>(),
+            vec![Move::new(2, 0), Move::new(2, 1)]
+        );
+    }
+
+    #[test]
+    fn test_mirror_horizontal() {
+        assert_eq!(Move::new(0, 2).mirror_horizontal(7), Move::new(6, 2));
+        assert_eq!(Move::new(3, 0).mirror_horizontal(7), Move::new(3, 0));
+        assert_eq!(Move::new(1, 5).mirror_horizontal(4), Move::new(2, 5));
+
+        assert_eq!(Move::NULL.mirror_horizontal(7), Move::NULL);
+        assert_eq!(Move::new(7, 0).mirror_horizontal(7), Move::new(7, 0));
+    }
+}
@@ -1,55 +1,275 @@
 use crate::player::Player;
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum GameOutcome {
     RedWin,
     YellowWin,
     Draw,
+    /// Yellow resigned.
+    RedWinByResignation,
+    /// Red resigned.
+    YellowWinByResignation,
+    /// The game was ended as a draw before the board filled.
+    ClaimedDraw,
+}
+
+/// A game result as seen by one player.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum RelativeResult {
+    Win,
+    Loss,
+    Draw,
+}
+
+impl core::fmt::Display for RelativeResult {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            RelativeResult::Win => write!(f, "win"),
+            RelativeResult::Loss => write!(f, "loss"),
+            RelativeResult::Draw => write!(f, "draw"),
+        }
+    }
 }
 
 impl GameOutcome {
+    /// The outcome in which `player` wins.
+    pub fn win_for(player: Player) -> GameOutcome {
+        match player {
+            Player::Red => GameOutcome::RedWin,
+            Player::Yellow => GameOutcome::YellowWin,
+        }
+    }
+
+    /// The outcome in which `player` resigns.
+    pub fn resignation_by(player: Player) -> GameOutcome {
+        match player {
+            Player::Red => GameOutcome::YellowWinByResignation,
+            Player::Yellow => GameOutcome::RedWinByResignation,
+        }
+    }
+
+    /// The same outcome with Red and Yellow exchanged. Draws are unchanged.
+    pub fn swap_players(&self) -> GameOutcome {
+        match self {
+            GameOutcome::RedWin => GameOutcome::YellowWin,
+            GameOutcome::YellowWin => GameOutcome::RedWin,
+            GameOutcome::RedWinByResignation => GameOutcome::YellowWinByResignation,
+            GameOutcome::YellowWinByResignation => GameOutcome::RedWinByResignation,
+            GameOutcome::Draw | GameOutcome::ClaimedDraw => *self,
+        }
+    }
+
     pub fn winner(&self) -> Option<Player> {
         match self {
-            GameOutcome::RedWin => Some(Player::Red),
-            GameOutcome::YellowWin => Some(Player::Yellow),
-            GameOutcome::Draw => None,
+            GameOutcome::RedWin | GameOutcome::RedWinByResignation => Some(Player::Red),
+            GameOutcome::YellowWin | GameOutcome::YellowWinByResignation => Some(Player::Yellow),
+            GameOutcome::Draw | GameOutcome::ClaimedDraw => None,
         }
     }
 
+    /// The losing player, or `None` for a draw.
+    pub fn loser(&self) -> Option<Player> {
+        self.winner().map(|player| player.opposite())
+    }
+
+    /// True if one player won, however the game ended.
+    pub fn is_decisive(&self) -> bool {
+        self.winner().is_some()
+    }
+
     pub fn encode_winner_absolute(&self) -> f32 {
-        match self {
-            GameOutcome::RedWin => 1.0,
-            GameOutcome::YellowWin => -1.0,
-            GameOutcome::Draw => 0.0,
+        match self.winner() {
+            Some(Player::Red) => 1.0,
+            Some(Player::Yellow) => -1.0,
+            None => 0.0,
         }
     }
 
     pub fn encode_winner_from_perspective(&self, perspective: Player) -> f32 {
-        match perspective {
-            Player::Red => match self {
-                GameOutcome::RedWin => 1.0,
-                GameOutcome::YellowWin => -1.0,
-                GameOutcome::Draw => 0.0,
-            },
-            Player::Yellow => match self {
-                GameOutcome::RedWin => -1.0,
-                GameOutcome::YellowWin => 1.0,
-                GameOutcome::Draw => 0.0,
-            },
+        match self.winner() {
+            Some(winner) if winner == perspective => 1.0,
+            Some(_) => -1.0,
+            None => 0.0,
+        }
+    }
+
+    /// Categorical counterpart of `encode_winner_from_perspective`.
+    pub fn relative_to(&self, player: Player) -> RelativeResult {
+        match self.winner() {
+            Some(winner) if winner == player => RelativeResult::Win,
+            Some(_) => RelativeResult::Loss,
+            None => RelativeResult::Draw,
+        }
+    }
+
+    /// Result token in the usual notation: "1-0" for a Red win, "0-1" for a
+    /// Yellow win and "1/2-1/2" for a draw, however the game ended.
+    pub fn result_string(&self) -> &'static str {
+        match self.winner() {
+            Some(Player::Red) => "1-0",
+            Some(Player::Yellow) => "0-1",
+            None => "1/2-1/2",
         }
     }
 
     pub fn is_draw(&self) -> bool {
-        matches!(self, GameOutcome::Draw)
+        matches!(self, GameOutcome::Draw | GameOutcome::ClaimedDraw)
+    }
+
+    pub fn is_resignation(&self) -> bool {
+        matches!(
+            self,
+            GameOutcome::RedWinByResignation | GameOutcome::YellowWinByResignation
+        )
+    }
+
+    /// True if the outcome follows from the stones on the board (four in a
+    /// row or a full board), false for resignations and claimed draws.
+    pub fn is_decided_on_board(&self) -> bool {
+        matches!(
+            self,
+            GameOutcome::RedWin | GameOutcome::YellowWin | GameOutcome::Draw
+        )
     }
 }
 
-impl std::fmt::Display for GameOutcome {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Display for GameOutcome {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         match self {
             GameOutcome::RedWin => write!(f, "Red wins"),
             GameOutcome::YellowWin => write!(f, "Yellow wins"),
             GameOutcome::Draw => write!(f, "Draw"),
+            GameOutcome::RedWinByResignation => write!(f, "Red wins by resignation"),
+            GameOutcome::YellowWinByResignation => write!(f, "Yellow wins by resignation"),
+            GameOutcome::ClaimedDraw => write!(f, "Draw (claimed)"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resignation_scores_like_win() {
+        assert_eq!(
+            GameOutcome::RedWinByResignation.encode_winner_absolute(),
+            GameOutcome::RedWin.encode_winner_absolute()
+        );
+        assert_eq!(
+            GameOutcome::YellowWinByResignation.encode_winner_absolute(),
+            GameOutcome::YellowWin.encode_winner_absolute()
+        );
+
+        for perspective in [Player::Red, Player::Yellow] {
+            assert_eq!(
+                GameOutcome::RedWinByResignation.encode_winner_from_perspective(perspective),
+                GameOutcome::RedWin.encode_winner_from_perspective(perspective)
+            );
+            assert_eq!(
+                GameOutcome::YellowWinByResignation.encode_winner_from_perspective(perspective),
+                GameOutcome::YellowWin.encode_winner_from_perspective(perspective)
+            );
+            assert_eq!(
+                GameOutcome::ClaimedDraw.encode_winner_from_perspective(perspective),
+                0.0
+            );
+        }
+    }
+
+    #[test]
+    fn test_resignation_by() {
+        assert_eq!(
+            GameOutcome::resignation_by(Player::Red).winner(),
+            Some(Player::Yellow)
+        );
+        assert_eq!(
+            GameOutcome::resignation_by(Player::Yellow).winner(),
+            Some(Player::Red)
+        );
+        assert!(GameOutcome::resignation_by(Player::Red).is_resignation());
+        assert!(GameOutcome::ClaimedDraw.is_draw());
+        assert!(!GameOutcome::ClaimedDraw.is_decided_on_board());
+    }
+
+    #[test]
+    fn test_result_string() {
+        assert_eq!(GameOutcome::RedWin.result_string(), "1-0");
+        assert_eq!(GameOutcome::YellowWin.result_string(), "0-1");
+        assert_eq!(GameOutcome::Draw.result_string(), "1/2-1/2");
+        assert_eq!(GameOutcome::RedWinByResignation.result_string(), "1-0");
+        assert_eq!(GameOutcome::YellowWinByResignation.result_string(), "0-1");
+        assert_eq!(GameOutcome::ClaimedDraw.result_string(), "1/2-1/2");
+    }
+
+    #[test]
+    fn test_relative_to() {
+        assert_eq!(
+            GameOutcome::RedWin.relative_to(Player::Red),
+            RelativeResult::Win
+        );
+        assert_eq!(
+            GameOutcome::RedWin.relative_to(Player::Yellow),
+            RelativeResult::Loss
+        );
+        assert_eq!(
+            GameOutcome::YellowWin.relative_to(Player::Red),
+            RelativeResult::Loss
+        );
+        assert_eq!(
+            GameOutcome::YellowWin.relative_to(Player::Yellow),
+            RelativeResult::Win
+        );
+        for player in [Player::Red, Player::Yellow] {
+            assert_eq!(GameOutcome::Draw.relative_to(player), RelativeResult::Draw);
+            assert_eq!(
+                GameOutcome::ClaimedDraw.relative_to(player),
+                RelativeResult::Draw
+            );
+            assert_eq!(
+                GameOutcome::resignation_by(player).relative_to(player),
+                RelativeResult::Loss
+            );
+        }
+        assert_eq!(RelativeResult::Win.to_string(), "win");
+        assert_eq!(RelativeResult::Loss.to_string(), "loss");
+        assert_eq!(RelativeResult::Draw.to_string(), "draw");
+    }
+
+    #[test]
+    fn test_swap_players() {
+        let all = [
+            GameOutcome::RedWin,
+            GameOutcome::YellowWin,
+            GameOutcome::Draw,
+            GameOutcome::RedWinByResignation,
+            GameOutcome::YellowWinByResignation,
+            GameOutcome::ClaimedDraw,
+        ];
+        for outcome in all {
+            let swapped = outcome.swap_players();
+            assert_eq!(swapped.swap_players(), outcome);
+            assert_eq!(swapped.winner(), outcome.winner().map(|p| p.opposite()));
+            assert_eq!(swapped.is_resignation(), outcome.is_resignation());
+            assert_eq!(swapped.is_draw(), outcome.is_draw());
+        }
+    }
+
+    #[test]
+    fn test_loser_and_is_decisive() {
+        for (outcome, loser) in [
+            (GameOutcome::RedWin, Some(Player::Yellow)),
+            (GameOutcome::RedWinByResignation, Some(Player::Yellow)),
+            (GameOutcome::YellowWin, Some(Player::Red)),
+            (GameOutcome::YellowWinByResignation, Some(Player::Red)),
+            (GameOutcome::Draw, None),
+            (GameOutcome::ClaimedDraw, None),
+        ] {
+            assert_eq!(outcome.loser(), loser, "{}", outcome);
+            assert_eq!(outcome.is_decisive(), loser.is_some(), "{}", outcome);
+            assert_eq!(outcome.is_decisive(), !outcome.is_draw(), "{}", outcome);
         }
     }
 }
@@ -1,4 +1,5 @@
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(i8)]
 pub enum Player {
     Red = 1,
@@ -6,6 +7,23 @@ pub enum Player {
 }
 
 impl Player {
+    /// Both players, Red first.
+    pub const ALL: [Player; 2] = [Player::Red, Player::Yellow];
+
+    /// Iterate over both players, Red first.
+    pub fn iter() -> impl Iterator<Item = Player> {
+        Player::ALL.into_iter()
+    }
+
+    /// Position of the player in `Player::ALL` (Red 0, Yellow 1), for
+    /// indexing per-player arrays.
+    pub fn index(&self) -> usize {
+        match self {
+            Player::Red => 0,
+            Player::Yellow => 1,
+        }
+    }
+
     pub fn opposite(&self) -> Player {
         match self {
             Player::Red => Player::Yellow,
@@ -20,6 +38,8 @@ impl Player {
         }
     }
 
+    /// Inverse of `to_char`, accepting either case. Empty cells (`.`) and
+    /// any other character give `None`.
     pub fn from_char(c: char) -> Option<Player> {
         match c {
             'R' | 'r' => Some(Player::Red),
@@ -37,8 +57,8 @@ impl Player {
     }
 }
 
-impl std::fmt::Display for Player {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Display for Player {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         let player_str = match self {
             Player::Red => "Red",
             Player::Yellow => "Yellow",
@@ -46,3 +66,37 @@ impl std::fmt::Display for Player {
         write!(f, "{}", player_str)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_char_round_trip() {
+        for player in [Player::Red, Player::Yellow] {
+            assert_eq!(Player::from_char(player.to_char()), Some(player));
+            assert_eq!(
+                Player::from_char(player.to_char().to_ascii_lowercase()),
+                Some(player)
+            );
+        }
+    }
+
+    #[test]
+    fn test_all_and_index() {
+        assert_eq!(Player::ALL, [Player::Red, Player::Yellow]);
+        assert_eq!(Player::iter().collect::<Vec<_>>(), Player::ALL.to_vec());
+        for (i, player) in Player::iter().enumerate() {
+            assert_eq!(player.index(), i);
+            assert_eq!(Player::ALL[player.index()], player);
+        }
+        assert_eq!(Player::Red.opposite().index(), Player::Yellow.index());
+    }
+
+    #[test]
+    fn test_from_char_rejects_invalid() {
+        for c in ['.', ' ', 'X', 'b', '1', 'Ŕ'] {
+            assert_eq!(Player::from_char(c), None);
+        }
+    }
+}
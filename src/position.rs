@@ -9,6 +9,8 @@ impl Position {
         Position { col, row }
     }
 
+    /// Inverse of `to_index`: recover the cell for a row-major bit index,
+    /// e.g. one yielded by `Bitboard::iter_ones`.
     pub fn from_index(index: usize, width: u8) -> Self {
         Position {
             col: (index % width as usize) as u8,
@@ -16,6 +18,8 @@ impl Position {
         }
     }
 
+    /// Row-major bit index of this cell: row 0 is the bottom row, and
+    /// `(col, row)` maps to `row * width + col`.
     pub fn to_index(&self, width: u8) -> usize {
         self.row as usize * width as usize + self.col as usize
     }
@@ -24,3 +28,29 @@ impl Position {
         self.col < width && self.row < height
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_round_trip(width: u8, height: u8) {
+        for row in 0..height {
+            for col in 0..width {
+                let pos = Position::new(col, row);
+                let index = pos.to_index(width);
+                assert_eq!(index, row as usize * width as usize + col as usize);
+                assert_eq!(Position::from_index(index, width), pos);
+            }
+        }
+    }
+
+    #[test]
+    fn test_index_round_trip_7x6() {
+        assert_round_trip(7, 6);
+    }
+
+    #[test]
+    fn test_index_round_trip_9x9() {
+        assert_round_trip(9, 9);
+    }
+}
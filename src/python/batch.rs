@@ -0,0 +1,40 @@
+use super::*;
+
+/// Apply one action to each game, returning whether each action was applied.
+/// The GIL is released while the batch is processed.
+#[pyfunction]
+pub fn step_many(
+    py: Python<'_>,
+    mut games: Vec<PyRefMut<'_, PyGame>>,
+    actions: Vec<usize>,
+) -> PyResult<Vec<bool>> {
+    if games.len() != actions.len() {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+            "Got {} games but {} actions",
+            games.len(),
+            actions.len()
+        )));
+    }
+
+    let mut inners: Vec<&mut GameInner> = games.iter_mut().map(|g| &mut g.inner).collect();
+    Ok(py.detach(move || {
+        inners
+            .iter_mut()
+            .zip(actions)
+            .map(|(inner, action)| inner.apply_action(action))
+            .collect()
+    }))
+}
+
+/// Legal-action masks for each game. Each mask has one entry per action
+/// (`total_actions()` of that game), true where the action is legal.
+#[pyfunction]
+pub fn legal_action_masks(py: Python<'_>, games: Vec<PyRef<'_, PyGame>>) -> Vec<Vec<bool>> {
+    let inners: Vec<&GameInner> = games.iter().map(|g| &g.inner).collect();
+    py.detach(move || {
+        inners
+            .iter()
+            .map(|inner| inner.legal_action_mask())
+            .collect()
+    })
+}
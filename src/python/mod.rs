@@ -1,8 +1,8 @@
 use super::*;
-use crate::bitboard::nw_for_board;
+use crate::bitboard::{nw_for_board, BoardGeometry};
 use crate::board::Board;
 use crate::encode;
-use crate::game::Game;
+use crate::game::{Game, MoveError};
 use crate::limits::{board_dimension_is_valid, MAX_BOARD_DIMENSION, MIN_BOARD_DIMENSION};
 use crate::outcome::GameOutcome;
 use crate::player::Player;
@@ -39,6 +39,27 @@ fn validate_board_dimensions(width: usize, height: usize) -> PyResult<(u8, u8)>
     Ok((width, height))
 }
 
+/// Convert a player int from Python, raising `ValueError` if it is not a player.
+fn player_from_int(value: i8) -> PyResult<Player> {
+    Player::from_int(value).ok_or_else(|| {
+        PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("Invalid player value {}", value))
+    })
+}
+
+/// Error for a pickled state that cannot be restored.
+fn invalid_state(err: impl std::fmt::Display) -> PyErr {
+    PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("Invalid pickled state: {}", err))
+}
+
+/// Number of 64-bit words backing a board of the given size, i.e. which
+/// dispatch variant a `Game` or `Board` of that size uses.
+#[pyfunction]
+#[pyo3(name = "nw_for_board")]
+pub fn py_nw_for_board(width: usize, height: usize) -> PyResult<usize> {
+    let (width, height) = validate_board_dimensions(width, height)?;
+    Ok(nw_for_board(width, height))
+}
+
 // -----------------------------------------------------------------------
 // Enum dispatch via paste! for Game<NW> and Board<NW>
 // -----------------------------------------------------------------------
@@ -51,9 +72,12 @@ macro_rules! define_dispatch {
                 $( [<Nw $nw>](Game<$nw>), )*
             }
 
+            /// A board together with its geometry, which is fixed at construction.
+            /// The geometry is boxed because it grows with `NW`, which would
+            /// otherwise make every variant as large as the 16-word one.
             #[derive(Clone, Debug)]
             pub(super) enum BoardInner {
-                $( [<Nw $nw>](Board<$nw>), )*
+                $( [<Nw $nw>](Board<$nw>, Box<BoardGeometry<$nw>>), )*
             }
 
             macro_rules! dispatch_game {
@@ -75,7 +99,12 @@ macro_rules! define_dispatch {
             macro_rules! dispatch_board {
                 ($self_:expr, $b:ident => $body:expr) => {
                     match $self_ {
-                        $( BoardInner::[<Nw $nw>]($b) => $body, )*
+                        $( BoardInner::[<Nw $nw>]($b, _) => $body, )*
+                    }
+                };
+                ($self_:expr, $b:ident, $geo:ident => $body:expr) => {
+                    match $self_ {
+                        $( BoardInner::[<Nw $nw>]($b, $geo) => $body, )*
                     }
                 };
             }
@@ -83,7 +112,12 @@ macro_rules! define_dispatch {
             macro_rules! dispatch_board_mut {
                 ($self_:expr, $b:ident => $body:expr) => {
                     match $self_ {
-                        $( BoardInner::[<Nw $nw>]($b) => $body, )*
+                        $( BoardInner::[<Nw $nw>]($b, _) => $body, )*
+                    }
+                };
+                ($self_:expr, $b:ident, $geo:ident => $body:expr) => {
+                    match $self_ {
+                        $( BoardInner::[<Nw $nw>]($b, $geo) => $body, )*
                     }
                 };
             }
@@ -99,7 +133,10 @@ macro_rules! define_dispatch {
             pub(super) fn make_board_inner(width: u8, height: u8) -> BoardInner {
                 let nw = nw_for_board(width, height);
                 match nw {
-                    $( $nw => BoardInner::[<Nw $nw>](Board::new(width, height)), )*
+                    $( $nw => BoardInner::[<Nw $nw>](
+                        Board::new(width, height),
+                        Box::new(BoardGeometry::new(width, height)),
+                    ), )*
                     _ => unreachable!("NW out of range: {}", nw),
                 }
             }
@@ -107,7 +144,10 @@ macro_rules! define_dispatch {
             macro_rules! game_to_board_inner {
                 ($game_inner:expr) => {
                     match $game_inner {
-                        $( GameInner::[<Nw $nw>](g) => BoardInner::[<Nw $nw>](*g.board()), )*
+                        $( GameInner::[<Nw $nw>](g) => BoardInner::[<Nw $nw>](
+                            *g.board(),
+                            Box::new(*g.geo()),
+                        ), )*
                     }
                 };
             }
@@ -117,12 +157,14 @@ macro_rules! define_dispatch {
 
 define_dispatch!(1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16);
 
+mod batch;
 mod py_board;
 mod py_game;
 mod py_game_outcome;
 mod py_move;
 mod symmetry;
 
+pub use batch::{legal_action_masks, step_many};
 pub use py_board::PyBoard;
 pub use py_game::PyGame;
 pub use py_game_outcome::PyGameOutcome;
@@ -29,6 +29,41 @@ impl PyBoard {
         }
     }
 
+    /// Build a board from `grid[row][col]`, with row 0 at the bottom, as
+    /// returned by `Game.to_grid`. Raises `ValueError` for a ragged grid, a
+    /// size outside the supported range, an unknown player value or a stone
+    /// with an empty cell below it.
+    #[staticmethod]
+    pub fn from_grid(grid: Vec<Vec<Option<i8>>>) -> PyResult<Self> {
+        let width = grid.first().map_or(0, Vec::len);
+        if let Some((row, cells)) = grid.iter().enumerate().find(|(_, r)| r.len() != width) {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                "Row {} has {} cells, expected {}",
+                row,
+                cells.len(),
+                width
+            )));
+        }
+        let (width, height) = validate_board_dimensions(width, grid.len())?;
+
+        let mut inner = make_board_inner(width, height);
+        for (row, cells) in grid.iter().enumerate() {
+            for (col, &cell) in cells.iter().enumerate() {
+                let Some(value) = cell else { continue };
+                let player = player_from_int(value)?;
+                if row > 0 && grid[row - 1][col].is_none() {
+                    return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                        "Stone at ({}, {}) has an empty cell below it",
+                        col, row
+                    )));
+                }
+                let pos = Position::new(col as u8, row as u8);
+                dispatch_board_mut!(&mut inner, b => b.set_piece(&pos, Some(player)));
+            }
+        }
+        Ok(PyBoard { inner })
+    }
+
     pub fn width(&self) -> usize {
         dispatch_board!(&self.inner, b => b.width() as usize)
     }
@@ -42,10 +77,11 @@ impl PyBoard {
         dispatch_board!(&self.inner, b => b.get_piece(&pos).map(|p| p as i8))
     }
 
-    pub fn set_piece(&mut self, col: usize, row: usize, piece: Option<i8>) {
+    pub fn set_piece(&mut self, col: usize, row: usize, piece: Option<i8>) -> PyResult<()> {
         let pos = Position::new(col as u8, row as u8);
-        let player = piece.map(|p| Player::from_int(p).expect("Invalid player value"));
-        dispatch_board_mut!(&mut self.inner, b => b.set_piece(&pos, player))
+        let player = piece.map(player_from_int).transpose()?;
+        dispatch_board_mut!(&mut self.inner, b => b.set_piece(&pos, player));
+        Ok(())
     }
 
     pub fn clear(&mut self) {
@@ -53,10 +89,7 @@ impl PyBoard {
     }
 
     pub fn is_board_full(&self) -> bool {
-        dispatch_board!(&self.inner, b => {
-            let geo = crate::bitboard::BoardGeometry::new(b.width(), b.height());
-            b.is_board_full(&geo)
-        })
+        dispatch_board!(&self.inner, b, geo => b.is_board_full(geo))
     }
 
     pub fn is_column_full(&self, col: usize) -> bool {
@@ -66,12 +99,77 @@ impl PyBoard {
     }
 
     pub fn column_height(&self, col: usize) -> usize {
+        dispatch_board!(&self.inner, b, geo => b.column_height(col as u8, geo) as usize)
+    }
+
+    /// Stack height of every column, left to right.
+    pub fn heights(&self) -> Vec<usize> {
+        dispatch_board!(&self.inner, b, geo => {
+            b.height_profile(geo)[..b.width() as usize]
+                .iter()
+                .map(|&h| h as usize)
+                .collect()
+        })
+    }
+
+    pub fn count(&self, player: i8) -> PyResult<u32> {
+        let player = player_from_int(player)?;
+        Ok(dispatch_board!(&self.inner, b => b.count(player)))
+    }
+
+    /// Stones as `(col, row, player)`: every Red stone, then every Yellow one,
+    /// each in row-major order from the bottom.
+    pub fn pieces(&self) -> Vec<(usize, usize, i8)> {
         dispatch_board!(&self.inner, b => {
-            let geo = crate::bitboard::BoardGeometry::new(b.width(), b.height());
-            b.column_height(col as u8, &geo) as usize
+            b.iter_pieces()
+                .map(|(pos, player)| (pos.col as usize, pos.row as usize, player as i8))
+                .collect()
         })
     }
 
+    /// Occupied cells as `(col, row)` pairs, in row-major order from the bottom.
+    pub fn occupied_indices(&self) -> Vec<(usize, usize)> {
+        dispatch_board!(&self.inner, b => {
+            b.occupied_bits()
+                .iter_ones()
+                .map(|i| {
+                    let pos = Position::from_index(i, b.width());
+                    (pos.col as usize, pos.row as usize)
+                })
+                .collect()
+        })
+    }
+
+    pub fn total_count(&self) -> u32 {
+        dispatch_board!(&self.inner, b => b.total_count())
+    }
+
+    pub fn __copy__(&self) -> PyBoard {
+        self.clone()
+    }
+
+    pub fn __deepcopy__(&self, _memo: &Bound<'_, PyAny>) -> PyBoard {
+        self.clone()
+    }
+
+    /// Constructor arguments for unpickling, so the restored board has the
+    /// same bitboard size as the original before `__setstate__` runs.
+    pub fn __getnewargs__(&self) -> (usize, usize) {
+        (self.width(), self.height())
+    }
+
+    pub fn __getstate__(&self) -> PyResult<String> {
+        dispatch_board!(&self.inner, b => serde_json::to_string(b).map_err(invalid_state))
+    }
+
+    pub fn __setstate__(&mut self, state: &str) -> PyResult<()> {
+        dispatch_board_mut!(&mut self.inner, b, geo => {
+            *b = serde_json::from_str(state).map_err(invalid_state)?;
+            **geo = BoardGeometry::new(b.width(), b.height());
+        });
+        Ok(())
+    }
+
     pub fn __str__(&self) -> String {
         dispatch_board!(&self.inner, b => b.to_string())
     }
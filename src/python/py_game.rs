@@ -1,4 +1,6 @@
 use super::*;
+use numpy::ndarray::Array3;
+use numpy::{IntoPyArray, PyArray3};
 
 #[pyclass(name = "Game")]
 pub struct PyGame {
@@ -35,10 +37,21 @@ impl PyGame {
         dispatch_game!(&self.inner, g => g.get_piece(&pos))
     }
 
-    pub fn set_piece(&mut self, col: usize, row: usize, piece: Option<i8>) {
+    /// The whole board as `grid[row][col]`, with row 0 at the bottom.
+    pub fn to_grid(&self) -> Vec<Vec<Option<i8>>> {
+        dispatch_game!(&self.inner, g => {
+            g.to_grid()
+                .into_iter()
+                .map(|row| row.into_iter().map(|p| p.map(|p| p as i8)).collect())
+                .collect()
+        })
+    }
+
+    pub fn set_piece(&mut self, col: usize, row: usize, piece: Option<i8>) -> PyResult<()> {
         let pos = Position::new(col as u8, row as u8);
-        let player = piece.map(|p| Player::from_int(p).expect("Invalid player value"));
-        dispatch_game_mut!(&mut self.inner, g => g.set_piece(&pos, player))
+        let player = piece.map(player_from_int).transpose()?;
+        dispatch_game_mut!(&mut self.inner, g => g.set_piece(&pos, player));
+        Ok(())
     }
 
     pub fn turn(&self) -> i8 {
@@ -49,26 +62,47 @@ impl PyGame {
         dispatch_game!(&self.inner, g => g.is_over())
     }
 
+    pub fn ply(&self) -> usize {
+        dispatch_game!(&self.inner, g => g.ply())
+    }
+
+    pub fn move_number(&self) -> usize {
+        dispatch_game!(&self.inner, g => g.move_number())
+    }
+
     // ---------------------------------------------------------------------
     // Unified Game Protocol Methods
     // ---------------------------------------------------------------------
 
     pub fn legal_action_indices(&self) -> Vec<usize> {
         dispatch_game!(&self.inner, g => {
-            g.legal_moves()
-                .into_iter()
-                .map(|m| encode::encode_move(&m))
+            g.cached_legal_moves()
+                .iter()
+                .map(encode::encode_move)
                 .collect()
         })
     }
 
+    /// One entry per action (`total_actions()` long), true where the action is legal.
+    pub fn legal_action_mask(&self) -> Vec<bool> {
+        self.inner.legal_action_mask()
+    }
+
     pub fn apply_action(&mut self, action: usize) -> bool {
-        dispatch_game_mut!(&mut self.inner, g => {
-            if let Some(move_) = encode::decode_move(action, g) {
-                g.make_move(&move_)
-            } else {
-                false
-            }
+        self.inner.apply_action(action)
+    }
+
+    /// Like `apply_action`, but returns `"ok"` on success or why the action
+    /// was rejected: `"out_of_range"`, `"column_full"` or `"game_over"`.
+    pub fn apply_action_checked(&mut self, action: usize) -> &'static str {
+        // Any action too large for a column index is off every board.
+        let col = u8::try_from(action).unwrap_or(u8::MAX);
+        dispatch_game_mut!(&mut self.inner, g => match g.try_drop(col) {
+            Ok(_) => "ok",
+            Err(MoveError::GameOver) => "game_over",
+            Err(MoveError::ColumnOutOfRange(_)) => "out_of_range",
+            // `try_drop` picks the landing row itself, so `WrongRow` cannot occur.
+            Err(MoveError::ColumnFull(_) | MoveError::WrongRow { .. }) => "column_full",
         })
     }
 
@@ -76,8 +110,17 @@ impl PyGame {
     // Encoding/decoding
     // ---------------------------------------------------------------------
 
-    pub fn encode_game_planes(&mut self) -> (Vec<f32>, usize, usize, usize) {
-        dispatch_game_mut!(&mut self.inner, g => encode::encode_game_planes(g))
+    /// Encoded input planes as a float32 array of shape `(num_planes, height, width)`.
+    pub fn encode_game_planes<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyArray3<f32>>> {
+        let (data, num_planes, height, width) =
+            dispatch_game!(&self.inner, g => encode::encode_game_planes_ref(g));
+        let planes = Array3::from_shape_vec((num_planes, height, width), data).map_err(|e| {
+            PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!(
+                "Failed to shape encoded planes: {}",
+                e
+            ))
+        })?;
+        Ok(planes.into_pyarray(py))
     }
 
     pub fn decode_action(&self, action: usize) -> Option<PyMove> {
@@ -106,27 +149,52 @@ impl PyGame {
         })
     }
 
-    pub fn reward_from_perspective(&self, perspective: i8) -> f32 {
-        dispatch_game!(&self.inner, g => {
+    /// `reward_absolute()` once the game is over, `None` while it is ongoing.
+    pub fn terminal_value(&self) -> Option<f32> {
+        dispatch_game!(&self.inner, g => g.terminal_value())
+    }
+
+    pub fn reward_from_perspective(&self, perspective: i8) -> PyResult<f32> {
+        let perspective = player_from_int(perspective)?;
+        Ok(dispatch_game!(&self.inner, g => {
             g.outcome()
-                .map(|o| {
-                    o.encode_winner_from_perspective(
-                        Player::from_int(perspective).expect("Invalid perspective"),
-                    )
-                })
+                .map(|o| o.encode_winner_from_perspective(perspective))
                 .unwrap_or(0.0)
-        })
+        }))
+    }
+
+    /// The winning player, or `None` while the game is ongoing or drawn.
+    pub fn winner(&self) -> Option<i8> {
+        dispatch_game!(&self.inner, g => g.winner().map(|player| player as i8))
+    }
+
+    pub fn result_string(&self) -> &'static str {
+        dispatch_game!(&self.inner, g => g.result_string())
     }
 
     pub fn outcome(&self) -> Option<PyGameOutcome> {
         dispatch_game!(&self.inner, g => g.outcome().map(|o| PyGameOutcome::from_outcome(o)))
     }
 
+    /// The outcome `move_` would produce without playing it, or `None` if the
+    /// game would continue. Raises `ValueError` if the move is illegal.
+    pub fn outcome_after(&self, move_: &PyMove) -> PyResult<Option<PyGameOutcome>> {
+        let outcome = dispatch_game!(&self.inner, g => g.outcome_after(&move_.as_move()));
+        outcome
+            .map(|o| o.map(PyGameOutcome::from_outcome))
+            .ok_or_else(|| {
+                PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                    "Illegal move {}",
+                    move_.as_move()
+                ))
+            })
+    }
+
     pub fn legal_moves(&self) -> Vec<PyMove> {
         dispatch_game!(&self.inner, g => {
-            g.legal_moves()
-                .into_iter()
-                .map(|m| PyMove::from_move(m))
+            g.cached_legal_moves()
+                .iter()
+                .map(|&m| PyMove::from_move(m))
                 .collect()
         })
     }
@@ -143,6 +211,29 @@ impl PyGame {
         dispatch_game_mut!(&mut self.inner, g => g.unmake_move())
     }
 
+    /// The most recent move, or `None` before the first move.
+    pub fn last_move(&self) -> Option<PyMove> {
+        dispatch_game!(&self.inner, g => g.last_move().map(PyMove::from_move))
+    }
+
+    /// Drop a stone into `col`, landing on the lowest empty row.
+    pub fn push(&mut self, col: usize) -> bool {
+        self.inner.apply_action(col)
+    }
+
+    /// Undo the last move and return it, or `None` if nothing was undone.
+    pub fn pop(&mut self) -> Option<PyMove> {
+        dispatch_game_mut!(&mut self.inner, g => {
+            let last = g.last_move()?;
+            g.unmake_move().then(|| PyMove::from_move(last))
+        })
+    }
+
+    /// Return to the starting position in place.
+    pub fn reset(&mut self) {
+        dispatch_game_mut!(&mut self.inner, g => g.reset())
+    }
+
     pub fn board(&self) -> PyBoard {
         PyBoard::from_inner(game_to_board_inner!(&self.inner))
     }
@@ -153,6 +244,36 @@ impl PyGame {
         }
     }
 
+    pub fn __copy__(&self) -> PyGame {
+        self.clone()
+    }
+
+    /// The game holds no Python objects, so a deep copy is the same full clone.
+    pub fn __deepcopy__(&self, _memo: &Bound<'_, PyAny>) -> PyGame {
+        self.clone()
+    }
+
+    // ---------------------------------------------------------------------
+    // Pickling
+    // ---------------------------------------------------------------------
+
+    /// Constructor arguments for unpickling, so the restored game has the
+    /// same bitboard size as the original before `__setstate__` runs.
+    pub fn __getnewargs__(&self) -> (usize, usize) {
+        (self.width(), self.height())
+    }
+
+    pub fn __getstate__(&self) -> PyResult<String> {
+        dispatch_game!(&self.inner, g => serde_json::to_string(g).map_err(invalid_state))
+    }
+
+    pub fn __setstate__(&mut self, state: &str) -> PyResult<()> {
+        dispatch_game_mut!(&mut self.inner, g => {
+            *g = serde_json::from_str(state).map_err(invalid_state)?;
+        });
+        Ok(())
+    }
+
     pub fn state_hash(&self) -> u64 {
         use std::hash::{Hash, Hasher};
         dispatch_game!(&self.inner, g => {
@@ -171,6 +292,12 @@ impl PyGame {
         })
     }
 
+    /// Hash of the board and side to move that is the same in every process,
+    /// unlike `hash()`. See `Board::stable_hash`.
+    pub fn stable_hash(&self) -> u64 {
+        dispatch_game!(&self.inner, g => g.board().stable_hash(g.turn()))
+    }
+
     pub fn __hash__(&self) -> u64 {
         use std::hash::{Hash, Hasher};
         dispatch_game!(&self.inner, g => {
@@ -201,3 +328,19 @@ impl PyGame {
         })
     }
 }
+
+impl GameInner {
+    pub(in crate::python) fn apply_action(&mut self, action: usize) -> bool {
+        dispatch_game_mut!(self, g => {
+            if let Some(move_) = encode::decode_move(action, g) {
+                g.make_move(&move_)
+            } else {
+                false
+            }
+        })
+    }
+
+    pub(in crate::python) fn legal_action_mask(&self) -> Vec<bool> {
+        dispatch_game!(self, g => g.legal_action_mask()[..g.width() as usize].to_vec())
+    }
+}
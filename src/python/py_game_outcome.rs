@@ -14,18 +14,59 @@ impl PyGameOutcome {
 
 #[pymethods]
 impl PyGameOutcome {
+    /// Construct from a variant name such as `"RedWin"`, as produced by
+    /// `__getnewargs__`. Outcomes are normally obtained from `Game.outcome()`.
+    #[new]
+    pub fn new(name: &str) -> PyResult<Self> {
+        let outcome = match name {
+            "RedWin" => GameOutcome::RedWin,
+            "YellowWin" => GameOutcome::YellowWin,
+            "Draw" => GameOutcome::Draw,
+            "RedWinByResignation" => GameOutcome::RedWinByResignation,
+            "YellowWinByResignation" => GameOutcome::YellowWinByResignation,
+            "ClaimedDraw" => GameOutcome::ClaimedDraw,
+            _ => {
+                return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                    "Unknown game outcome '{}'",
+                    name
+                )))
+            }
+        };
+        Ok(PyGameOutcome { outcome })
+    }
+
+    pub fn __getnewargs__(&self) -> (String,) {
+        (format!("{:?}", self.outcome),)
+    }
+
     pub fn winner(&self) -> Option<i8> {
         self.outcome.winner().map(|player| player as i8)
     }
 
+    pub fn loser(&self) -> Option<i8> {
+        self.outcome.loser().map(|player| player as i8)
+    }
+
+    pub fn is_decisive(&self) -> bool {
+        self.outcome.is_decisive()
+    }
+
     pub fn encode_winner_absolute(&self) -> f32 {
         self.outcome.encode_winner_absolute()
     }
 
-    pub fn encode_winner_from_perspective(&self, perspective: i8) -> f32 {
-        self.outcome.encode_winner_from_perspective(
-            Player::from_int(perspective).expect("Unrecognized perspective"),
-        )
+    pub fn encode_winner_from_perspective(&self, perspective: i8) -> PyResult<f32> {
+        Ok(self
+            .outcome
+            .encode_winner_from_perspective(player_from_int(perspective)?))
+    }
+
+    /// `"win"`, `"loss"` or `"draw"` from `player`'s point of view.
+    pub fn relative_to(&self, player: i8) -> PyResult<String> {
+        Ok(self
+            .outcome
+            .relative_to(player_from_int(player)?)
+            .to_string())
     }
 
     pub fn is_draw(&self) -> bool {
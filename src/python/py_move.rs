@@ -33,6 +33,12 @@ impl PyMove {
         self.move_.row as usize
     }
 
+    /// Whether the move's cell lies on `game`'s board, ignoring gravity and
+    /// whose turn it is.
+    pub fn is_valid_for(&self, game: &PyGame) -> bool {
+        dispatch_game!(&game.inner, g => self.move_.is_valid_for(g))
+    }
+
     // ---------------------------------------------------------------------
     // Encoding/decoding
     // ---------------------------------------------------------------------
@@ -58,17 +64,47 @@ impl PyMove {
     // ---------------------------------------------------------------------
 
     pub fn __str__(&self) -> String {
-        format!("col {}", self.move_.col)
+        format!("col {}, row {}", self.move_.col, self.move_.row)
     }
 
     pub fn __repr__(&self) -> String {
         format!("Move(col={}, row={})", self.move_.col, self.move_.row)
     }
 
+    pub fn __copy__(&self) -> PyMove {
+        self.clone()
+    }
+
+    pub fn __deepcopy__(&self, _memo: &Bound<'_, PyAny>) -> PyMove {
+        self.clone()
+    }
+
+    pub fn __getnewargs__(&self) -> (usize, usize) {
+        (self.col(), self.row())
+    }
+
     pub fn __eq__(&self, other: &PyMove) -> bool {
         self.move_ == other.move_
     }
 
+    // Ordering follows `Move`'s `Ord`: by column, then by row.
+
+    pub fn __lt__(&self, other: &PyMove) -> bool {
+        self.move_ < other.move_
+    }
+
+    pub fn __le__(&self, other: &PyMove) -> bool {
+        self.move_ <= other.move_
+    }
+
+    pub fn __gt__(&self, other: &PyMove) -> bool {
+        self.move_ > other.move_
+    }
+
+    pub fn __ge__(&self, other: &PyMove) -> bool {
+        self.move_ >= other.move_
+    }
+
     pub fn __hash__(&self) -> u64 {
         use std::hash::{Hash, Hasher};
         let mut hasher = std::collections::hash_map::DefaultHasher::new();
@@ -0,0 +1,160 @@
+//! Exact alpha-beta solver, intended as a reference for small boards.
+
+use crate::game::Game;
+use crate::outcome::RelativeResult;
+
+/// Solve `game` by a negamax alpha-beta search at most `max_depth` plies deep,
+/// trying moves center-first.
+///
+/// Returns the signed distance to the result with best play: `Some(k)` if the
+/// side to move wins with the `k`-th ply from now, `Some(-k)` if it loses on
+/// the `k`-th ply, and `Some(0)` for a draw (including a game that has already
+/// ended drawn). Returns `None` if the result cannot be proven within
+/// `max_depth` plies, or if the game has already been won, since no distance
+/// can tell that apart from a draw; check `Game::outcome` first.
+pub fn solve<const NW: usize>(game: &Game<NW>, max_depth: usize) -> Option<i32> {
+    if game.winner().is_some() {
+        return None;
+    }
+
+    // Internally a result `k` plies away scores `±(horizon - k)`, so quicker
+    // wins and slower losses are preferred; every score lies in
+    // `-horizon..=horizon`.
+    let horizon = game.width() as i32 * game.height() as i32 + 1;
+    let mut game = game.clone();
+    let value = negamax(&mut game, max_depth, 0, -horizon, horizon, horizon)?;
+    Some(match value {
+        v if v > 0 => horizon - v,
+        v if v < 0 => -(horizon + v),
+        _ => 0,
+    })
+}
+
+/// Negamax value of `game` from the side to move's point of view, or `None`
+/// if some line needed to establish it runs past `depth`.
+fn negamax<const NW: usize>(
+    game: &mut Game<NW>,
+    depth: usize,
+    ply: i32,
+    mut alpha: i32,
+    mut beta: i32,
+    horizon: i32,
+) -> Option<i32> {
+    if let Some(outcome) = game.outcome() {
+        return Some(match outcome.relative_to(game.turn()) {
+            RelativeResult::Win => horizon - ply,
+            RelativeResult::Loss => -(horizon - ply),
+            RelativeResult::Draw => 0,
+        });
+    }
+
+    // Nothing beats winning on the next ply, so a window entirely above that
+    // is refuted without searching, even at the depth limit.
+    beta = beta.min(horizon - ply - 1);
+    if alpha >= beta {
+        return Some(beta);
+    }
+    if depth == 0 {
+        return None;
+    }
+
+    let mut best: Option<i32> = None;
+    let mut resolved = true;
+    for move_ in game.legal_moves_ordered() {
+        game.make_move(&move_);
+        let child = negamax(game, depth - 1, ply + 1, -beta, -alpha, horizon);
+        game.unmake_move();
+
+        let Some(child) = child else {
+            resolved = false;
+            continue;
+        };
+        let value = -child;
+        best = Some(best.map_or(value, |b| b.max(value)));
+        alpha = alpha.max(value);
+        if alpha >= beta {
+            // A proven lower bound at or above `beta` refutes this line
+            // whatever the unresolved siblings turn out to be.
+            return Some(value);
+        }
+    }
+
+    if resolved {
+        best
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bitboard::nw_for_board;
+
+    type SmallGame = Game<{ nw_for_board(4, 4) }>;
+    type StdGame = Game<{ nw_for_board(7, 6) }>;
+
+    #[test]
+    fn test_solve_4x4_is_draw() {
+        let game = SmallGame::new(4, 4);
+        assert_eq!(solve(&game, 16), Some(0));
+    }
+
+    #[test]
+    fn test_solve_win_in_one() {
+        let mut game = StdGame::new(7, 6);
+        for col in [0, 1, 0, 1, 0, 1] {
+            game.try_drop(col)
+                .expect("test_solve_win_in_one: column must be playable");
+        }
+        assert_eq!(solve(&game, 1), Some(1));
+    }
+
+    #[test]
+    fn test_solve_loss_against_double_threat() {
+        // Red has open-ended three on the bottom row; Yellow can block only one end.
+        let mut game = StdGame::new(7, 6);
+        for col in [2, 2, 3, 3, 4] {
+            game.try_drop(col)
+                .expect("test_solve_loss_against_double_threat: column must be playable");
+        }
+        assert_eq!(solve(&game, 2), Some(-2));
+    }
+
+    #[test]
+    fn test_solve_depth_exceeded() {
+        let game = StdGame::new(7, 6);
+        assert_eq!(solve(&game, 2), None);
+    }
+
+    #[test]
+    fn test_solve_finished_game() {
+        let mut game = StdGame::new(7, 6);
+        for col in [0, 1, 0, 1, 0, 1, 0] {
+            game.try_drop(col)
+                .expect("test_solve_finished_game: column must be playable");
+        }
+        assert_eq!(solve(&game, 0), None);
+
+        let mut drawn = StdGame::new(7, 6);
+        for col in [3, 3] {
+            drawn
+                .try_drop(col)
+                .expect("test_solve_finished_game: column must be playable");
+        }
+        drawn.claim_draw();
+        assert_eq!(solve(&drawn, 0), Some(0));
+    }
+
+    #[test]
+    fn test_solve_leaves_game_untouched() {
+        let mut game = SmallGame::new(4, 4);
+        for col in [1, 2] {
+            game.try_drop(col)
+                .expect("test_solve_leaves_game_untouched: column must be playable");
+        }
+        let before = game.state_hash();
+        solve(&game, 6);
+        assert_eq!(game.state_hash(), before);
+    }
+}
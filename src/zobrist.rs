@@ -0,0 +1,87 @@
+use crate::limits::MAX_BOARD_DIMENSION;
+use crate::player::Player;
+
+/// Number of cells on the largest supported board.
+const MAX_CELLS: usize = MAX_BOARD_DIMENSION as usize * MAX_BOARD_DIMENSION as usize;
+
+/// Fixed seed for the key table, so keys are stable across runs and builds.
+const SEED: u64 = 0x5EED_C044_EC74_0000;
+
+/// One step of SplitMix64. Returns the next state and its output.
+const fn splitmix64(state: u64) -> (u64, u64) {
+    let state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    let mut z = state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    (state, z ^ (z >> 31))
+}
+
+/// Per-cell keys for Red (`[0]`) and Yellow (`[1]`), followed by the side-to-move key.
+const fn build_keys() -> ([[u64; MAX_CELLS]; 2], u64) {
+    let mut keys = [[0u64; MAX_CELLS]; 2];
+    let mut state = SEED;
+    let mut color = 0;
+    while color < 2 {
+        let mut i = 0;
+        while i < MAX_CELLS {
+            let (next, key) = splitmix64(state);
+            state = next;
+            keys[color][i] = key;
+            i += 1;
+        }
+        color += 1;
+    }
+    let (_, side_key) = splitmix64(state);
+    (keys, side_key)
+}
+
+const KEYS: ([[u64; MAX_CELLS]; 2], u64) = build_keys();
+
+static PIECE_KEYS: [[u64; MAX_CELLS]; 2] = KEYS.0;
+
+/// Key XORed in when Yellow is to move.
+pub const SIDE_TO_MOVE_KEY: u64 = KEYS.1;
+
+/// Key for a `player` stone on cell `index` (`row * width + col`).
+///
+/// XORing this into a Zobrist key toggles that stone: XOR it in when the stone
+/// is placed and XOR it again when it is removed.
+#[inline]
+pub fn piece_key(player: Player, index: usize) -> u64 {
+    debug_assert!(index < MAX_CELLS);
+    match player {
+        Player::Red => PIECE_KEYS[0][index],
+        Player::Yellow => PIECE_KEYS[1][index],
+    }
+}
+
+/// Key identifying the board dimensions, so that keys of equal stone
+/// patterns on differently sized boards (whose cell indices coincide) differ.
+#[inline]
+pub fn dimensions_key(width: u8, height: u8) -> u64 {
+    splitmix64(SEED ^ ((width as u64) << 8 | height as u64)).1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_keys_are_fixed() {
+        // Guards against accidental changes to the seed or generator, which
+        // would invalidate keys stored by users.
+        assert_eq!(piece_key(Player::Red, 0), 0xB749_5EB8_B48D_8BE1);
+        assert_eq!(SIDE_TO_MOVE_KEY, 0xAD97_2E41_6A06_FC9B);
+        assert_eq!(dimensions_key(7, 6), 0x5401_36EE_551B_D0AB);
+    }
+
+    #[test]
+    fn test_keys_are_distinct() {
+        let mut all: Vec<u64> = PIECE_KEYS.iter().flatten().copied().collect();
+        all.push(SIDE_TO_MOVE_KEY);
+        let len = all.len();
+        all.sort_unstable();
+        all.dedup();
+        assert_eq!(all.len(), len);
+    }
+}
@@ -0,0 +1,61 @@
+import random
+
+import pytest
+
+import spooky_connect4
+
+
+def mask_from_indices(game: spooky_connect4.Game) -> list[bool]:
+    legal = set(game.legal_action_indices())
+    return [action in legal for action in range(game.total_actions())]
+
+
+def test_step_many_applies_one_action_per_game() -> None:
+    games = [spooky_connect4.Game(width=7, height=6) for _ in range(3)]
+
+    results = spooky_connect4.step_many(games, [0, 3, 6])
+
+    assert results == [True, True, True]
+    assert games[0].get_piece(0, 0) == spooky_connect4.RED
+    assert games[1].get_piece(3, 0) == spooky_connect4.RED
+    assert games[2].get_piece(6, 0) == spooky_connect4.RED
+    for game in games:
+        assert game.turn() == spooky_connect4.YELLOW
+
+
+def test_step_many_reports_illegal_actions() -> None:
+    games = [spooky_connect4.Game(width=7, height=6), spooky_connect4.Game(width=7, height=6)]
+    for _ in range(6):
+        games[1].apply_action(2)
+
+    results = spooky_connect4.step_many(games, [7, 2])
+
+    assert results == [False, False]
+    assert games[0].turn() == spooky_connect4.RED
+
+
+def test_step_many_length_mismatch() -> None:
+    games = [spooky_connect4.Game(width=7, height=6)]
+    with pytest.raises(ValueError):
+        spooky_connect4.step_many(games, [0, 1])
+
+
+def test_legal_action_masks_match_indices() -> None:
+    rng = random.Random(0)
+    games = [spooky_connect4.Game(width=7, height=6), spooky_connect4.Game(width=9, height=5)]
+
+    for _ in range(60):
+        masks = spooky_connect4.legal_action_masks(games)
+        assert masks == [mask_from_indices(game) for game in games]
+
+        actions = []
+        for game in games:
+            legal = game.legal_action_indices()
+            actions.append(rng.choice(legal) if legal else 0)
+        spooky_connect4.step_many(games, actions)
+
+    for game in games:
+        mask = spooky_connect4.legal_action_masks([game])[0]
+        assert len(mask) == game.total_actions()
+        if game.is_over():
+            assert not any(mask)
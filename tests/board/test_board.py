@@ -1,3 +1,7 @@
+import pickle
+
+import pytest
+
 import spooky_connect4
 
 
@@ -23,3 +27,145 @@ def test_board_column_full() -> None:
     board = spooky_connect4.Board(width=7, height=6)
     for col in range(7):
         assert not board.is_column_full(col)
+
+
+def test_board_count() -> None:
+    board = spooky_connect4.Board(width=7, height=6)
+    assert board.count(spooky_connect4.RED) == 0
+    assert board.total_count() == 0
+
+    board.set_piece(0, 0, spooky_connect4.RED)
+    board.set_piece(1, 0, spooky_connect4.RED)
+    board.set_piece(0, 1, spooky_connect4.YELLOW)
+
+    assert board.count(spooky_connect4.RED) == 2
+    assert board.count(spooky_connect4.YELLOW) == 1
+    assert board.total_count() == 3
+
+
+def test_board_geometry_queries_repeated_across_sizes() -> None:
+    # Interleave sizes to check each board answers for its own dimensions.
+    sizes = [(7, 6), (9, 9), (4, 4), (7, 6), (19, 17)]
+    boards = [spooky_connect4.Board(width=w, height=h) for w, h in sizes]
+
+    for _ in range(3):
+        for board, (width, _height) in zip(boards, sizes, strict=True):
+            for col in range(width):
+                assert board.column_height(col) == 0
+            assert not board.is_board_full()
+
+    board = spooky_connect4.Board(width=4, height=4)
+    for col in range(4):
+        for row in range(4):
+            assert board.column_height(col) == row
+            board.set_piece(col, row, spooky_connect4.RED if (col + row) % 2 == 0 else spooky_connect4.YELLOW)
+        assert board.column_height(col) == 4
+    assert board.is_board_full()
+    assert not boards[0].is_board_full()
+
+
+def test_board_column_height_repeated_after_copy_and_pickle() -> None:
+    game = spooky_connect4.Game(width=9, height=7)
+    for col in [4, 4, 3, 8, 4]:
+        assert game.apply_action(col)
+    expected = [0, 0, 0, 1, 3, 0, 0, 0, 1]
+
+    board = game.board()
+    restored = pickle.loads(pickle.dumps(board))
+    for _ in range(3):
+        assert [board.column_height(col) for col in range(9)] == expected
+        assert [restored.column_height(col) for col in range(9)] == expected
+    assert not restored.is_board_full()
+
+
+def test_nw_for_board() -> None:
+    assert spooky_connect4.nw_for_board(7, 6) == 1
+    assert spooky_connect4.nw_for_board(8, 8) == 1
+    assert spooky_connect4.nw_for_board(9, 9) == 2
+    assert spooky_connect4.nw_for_board(19, 19) == 6
+    assert spooky_connect4.nw_for_board(32, 32) == 16
+
+
+def test_nw_for_board_rejects_invalid_size() -> None:
+    with pytest.raises(ValueError):
+        spooky_connect4.nw_for_board(3, 6)
+
+
+def test_board_occupied_indices() -> None:
+    for width, height in [(7, 6), (9, 9), (19, 19)]:
+        board = spooky_connect4.Board(width=width, height=height)
+        assert board.occupied_indices() == []
+
+        board.set_piece(width - 1, height - 1, spooky_connect4.YELLOW)
+        board.set_piece(1, 0, spooky_connect4.RED)
+        board.set_piece(0, 1, spooky_connect4.RED)
+        assert board.occupied_indices() == [(1, 0), (0, 1), (width - 1, height - 1)]
+
+
+def test_board_invalid_player_raises() -> None:
+    board = spooky_connect4.Board(width=7, height=6)
+    with pytest.raises(ValueError):
+        board.set_piece(0, 0, 2)
+    with pytest.raises(ValueError):
+        board.count(0)
+    assert board.get_piece(0, 0) is None
+
+
+def test_board_pieces() -> None:
+    board = spooky_connect4.Board(width=7, height=6)
+    assert board.pieces() == []
+
+    board.set_piece(3, 0, spooky_connect4.RED)
+    board.set_piece(3, 1, spooky_connect4.YELLOW)
+    board.set_piece(0, 0, spooky_connect4.YELLOW)
+    board.set_piece(6, 0, spooky_connect4.RED)
+
+    expected = [
+        (col, row, board.get_piece(col, row))
+        for player in [spooky_connect4.RED, spooky_connect4.YELLOW]
+        for row in range(6)
+        for col in range(7)
+        if board.get_piece(col, row) == player
+    ]
+    assert board.pieces() == expected
+    assert board.pieces() == [
+        (3, 0, spooky_connect4.RED),
+        (6, 0, spooky_connect4.RED),
+        (0, 0, spooky_connect4.YELLOW),
+        (3, 1, spooky_connect4.YELLOW),
+    ]
+
+
+def test_board_heights() -> None:
+    game = spooky_connect4.Game(width=9, height=7)
+    board = game.board()
+    assert board.heights() == [0] * 9
+
+    for col in [4, 4, 3, 8, 4, 0, 8]:
+        assert game.apply_action(col)
+    board = game.board()
+    assert board.heights() == [board.column_height(col) for col in range(9)]
+    assert board.heights() == [1, 0, 0, 1, 3, 0, 0, 0, 2]
+
+
+def test_board_from_grid() -> None:
+    game = spooky_connect4.Game(width=9, height=5)
+    for col in [4, 4, 3, 8, 4, 0, 8]:
+        assert game.apply_action(col)
+
+    board = spooky_connect4.Board.from_grid(game.to_grid())
+    assert (board.width(), board.height()) == (9, 5)
+    assert board.pieces() == game.board().pieces()
+    assert board.heights() == game.board().heights()
+
+
+def test_board_from_grid_rejects_invalid() -> None:
+    empty_row: list[int | None] = [None] * 7
+    with pytest.raises(ValueError):
+        spooky_connect4.Board.from_grid([empty_row] * 5 + [[None] * 6])
+    with pytest.raises(ValueError):
+        spooky_connect4.Board.from_grid([[None] * 3] * 6)
+    with pytest.raises(ValueError):
+        spooky_connect4.Board.from_grid([[2] + [None] * 6] + [empty_row] * 5)
+    with pytest.raises(ValueError):
+        spooky_connect4.Board.from_grid([empty_row, [spooky_connect4.RED] + [None] * 6] + [empty_row] * 4)
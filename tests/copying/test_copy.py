@@ -0,0 +1,46 @@
+import copy
+
+import spooky_connect4
+
+
+def test_game_copy_is_independent() -> None:
+    for copier in (copy.copy, copy.deepcopy):
+        game = spooky_connect4.Game(width=7, height=6)
+        for col in [3, 3, 2]:
+            assert game.push(col)
+        legal_before = game.legal_action_indices()
+        hash_before = game.state_hash()
+
+        duplicate = copier(game)
+        assert duplicate.state_hash() == hash_before
+        for col in [0, 0, 0, 0, 0, 0]:
+            assert duplicate.push(col)
+
+        assert game.legal_action_indices() == legal_before
+        assert game.state_hash() == hash_before
+        assert duplicate.legal_action_indices() != legal_before
+
+
+def test_board_copy_is_independent() -> None:
+    for copier in (copy.copy, copy.deepcopy):
+        board = spooky_connect4.Board(width=9, height=9)
+        board.set_piece(4, 0, spooky_connect4.RED)
+
+        duplicate = copier(board)
+        duplicate.set_piece(5, 0, spooky_connect4.YELLOW)
+        assert board.pieces() == [(4, 0, spooky_connect4.RED)]
+        assert duplicate.total_count() == 2
+
+
+def test_move_copy() -> None:
+    move = spooky_connect4.Move(3, 1)
+    assert copy.copy(move) == move
+    assert copy.deepcopy(move) == move
+
+
+def test_deepcopy_nested() -> None:
+    game = spooky_connect4.Game(width=7, height=6)
+    games = {"a": [game]}
+    copied = copy.deepcopy(games)
+    assert copied["a"][0].push(3)
+    assert game.ply() == 0
@@ -1,31 +1,44 @@
+import numpy as np
+
 import spooky_connect4
 
 
 def test_encode_state() -> None:
     game = spooky_connect4.Game(width=7, height=6)
-    data, num_planes, height, width = game.encode_game_planes()
-
-    # Should return flat data with shape metadata
-    assert isinstance(data, list)
-    assert num_planes == 17
-    assert height == 6
-    assert width == 7
-    assert len(data) == num_planes * height * width
+    planes = game.encode_game_planes()
 
-
-def get_plane_value(data: list[float], plane: int, row: int, col: int, height: int, width: int) -> float:
-    return data[plane * height * width + row * width + col]
+    # Should return a (num_planes, height, width) float32 array
+    assert isinstance(planes, np.ndarray)
+    assert planes.dtype == np.float32
+    assert planes.shape == (17, 6, 7)
 
 
 def test_encode_empty_game() -> None:
     game = spooky_connect4.Game(width=7, height=6)
-    data, _num_planes, height, width = game.encode_game_planes()
+    planes = game.encode_game_planes()
 
     # First two planes (current player and opponent) should be all zeros
-    for plane in range(2):
-        for row in range(height):
-            for col in range(width):
-                assert get_plane_value(data, plane, row, col, height, width) == 0.0
+    assert not planes[:2].any()
+
+
+def test_encode_after_two_moves() -> None:
+    game = spooky_connect4.Game(width=7, height=6)
+    game.make_move(spooky_connect4.Move(3, 0))
+    game.make_move(spooky_connect4.Move(3, 1))
+
+    planes = game.encode_game_planes()
+
+    assert planes.shape == (17, 6, 7)
+    # Red to move: own stone at the bottom of column 3, opponent's stone above it
+    assert planes[0, 0, 3] == 1.0
+    assert planes[1, 1, 3] == 1.0
+    assert planes[0].sum() == 1.0
+    assert planes[1].sum() == 1.0
+    # One ply back, Yellow's stone is not yet on the board
+    assert planes[2, 0, 3] == 1.0
+    assert planes[3].sum() == 0.0
+    # Color plane is all ones for Red
+    assert (planes[16] == 1.0).all()
 
 
 def test_encode_with_pieces() -> None:
@@ -35,17 +48,10 @@ def test_encode_with_pieces() -> None:
     move = spooky_connect4.Move(0, 0)
     game.make_move(move)
 
-    data, _num_planes, height, width = game.encode_game_planes()
+    planes = game.encode_game_planes()
 
     # Now should have a piece somewhere in the first two planes
-    has_piece = False
-    for plane in range(2):
-        for row in range(height):
-            for col in range(width):
-                if get_plane_value(data, plane, row, col, height, width) != 0.0:
-                    has_piece = True
-                    break
-    assert has_piece
+    assert planes[:2].any()
 
 
 def test_move_encode_decode() -> None:
@@ -64,14 +70,14 @@ def test_encode_different_players() -> None:
     game = spooky_connect4.Game(width=7, height=6)
 
     # Red's turn
-    data_red, _num_planes, _height, _width = game.encode_game_planes()
+    planes_red = game.encode_game_planes()
 
     # Make a move to switch to Yellow
     move = game.legal_moves()[0]
     game.make_move(move)
 
     # Yellow's turn
-    data_yellow, _, _, _ = game.encode_game_planes()
+    planes_yellow = game.encode_game_planes()
 
     # Encodings should be different after a move
-    assert data_red != data_yellow
+    assert not np.array_equal(planes_red, planes_yellow)
@@ -1,5 +1,7 @@
 import random
 
+import pytest
+
 import spooky_connect4
 
 
@@ -16,6 +18,54 @@ def test_game_legal_moves_initial() -> None:
     assert len(moves) == 7  # All 7 columns are available
 
 
+def test_game_legal_action_mask() -> None:
+    game = spooky_connect4.Game(width=7, height=6)
+    assert game.legal_action_mask() == [True] * 7
+
+    for _ in range(6):
+        game.apply_action(0)
+    assert game.legal_action_mask() == [False] + [True] * 6
+
+    for action in [1, 2, 1, 2, 1, 2, 1]:
+        game.apply_action(action)
+    assert game.is_over()
+    assert game.legal_action_mask() == [False] * 7
+
+
+def test_game_result_string() -> None:
+    game = spooky_connect4.Game(width=7, height=6)
+    assert game.result_string() == "*"
+
+    for action in [0, 1, 0, 1, 0, 1, 0]:
+        game.apply_action(action)
+    assert game.result_string() == "1-0"
+
+    game = spooky_connect4.Game(width=7, height=6)
+    for action in [6, 0, 1, 0, 1, 0, 1, 0]:
+        game.apply_action(action)
+    assert game.result_string() == "0-1"
+
+    game = spooky_connect4.Game(width=7, height=6)
+    for action in [0, 1, 2] * 6 + [3, 4, 5] * 6 + [6] * 6:
+        game.apply_action(action)
+    assert game.result_string() == "1/2-1/2"
+
+
+def test_game_ply_and_move_number() -> None:
+    game = spooky_connect4.Game(width=7, height=6)
+    assert game.ply() == 0
+    assert game.move_number() == 1
+
+    for action in [3, 3, 4]:
+        game.apply_action(action)
+    assert game.ply() == 3
+    assert game.move_number() == 2
+
+    game.unmake_move()
+    assert game.ply() == 2
+    assert game.move_number() == 2
+
+
 def test_game_make_move() -> None:
     game = spooky_connect4.Game(width=7, height=6)
     moves = game.legal_moves()
@@ -236,3 +286,223 @@ def test_board_representation() -> None:
     assert "|" in board_str
     assert "R" in board_str  # Red piece
     assert "Y" in board_str  # Yellow piece
+
+
+def test_reset() -> None:
+    game = spooky_connect4.Game(width=9, height=7)
+    for col in [0, 1, 0, 1, 0, 1, 0]:
+        assert game.apply_action(col)
+    assert game.is_over()
+
+    game.reset()
+    fresh = spooky_connect4.Game(width=9, height=7)
+    assert not game.is_over()
+    assert game.outcome() is None
+    assert game.turn() == fresh.turn()
+    assert game.ply() == 0
+    assert game.legal_action_indices() == fresh.legal_action_indices()
+    assert str(game.board()) == str(fresh.board())
+
+
+def test_push_and_pop() -> None:
+    game = spooky_connect4.Game(width=7, height=6)
+    assert game.pop() is None
+
+    assert game.push(3)
+    assert game.push(3)
+    assert game.push(4)
+    assert game.get_piece(3, 1) == spooky_connect4.YELLOW
+    assert game.ply() == 3
+
+    undone = game.pop()
+    assert undone is not None
+    assert (undone.col(), undone.row()) == (4, 0)
+    assert game.get_piece(4, 0) is None
+    assert game.turn() == spooky_connect4.RED
+
+    undone = game.pop()
+    assert undone is not None
+    assert (undone.col(), undone.row()) == (3, 1)
+    assert game.ply() == 1
+
+    assert not game.push(7)
+    for _ in range(5):
+        assert game.push(0)
+    assert game.push(0)
+    assert not game.push(0)
+
+
+def test_outcome_relative_to() -> None:
+    red, yellow = spooky_connect4.RED, spooky_connect4.YELLOW
+
+    red_win = spooky_connect4.GameOutcome("RedWin")
+    assert red_win.relative_to(red) == "win"
+    assert red_win.relative_to(yellow) == "loss"
+
+    yellow_win = spooky_connect4.GameOutcome("YellowWin")
+    assert yellow_win.relative_to(red) == "loss"
+    assert yellow_win.relative_to(yellow) == "win"
+
+    draw = spooky_connect4.GameOutcome("Draw")
+    assert draw.relative_to(red) == "draw"
+    assert draw.relative_to(yellow) == "draw"
+
+
+def test_outcome_loser_and_is_decisive() -> None:
+    red, yellow = spooky_connect4.RED, spooky_connect4.YELLOW
+
+    for name, winner, loser in [
+        ("RedWin", red, yellow),
+        ("YellowWinByResignation", yellow, red),
+        ("Draw", None, None),
+        ("ClaimedDraw", None, None),
+    ]:
+        outcome = spooky_connect4.GameOutcome(name)
+        assert outcome.winner() == winner
+        assert outcome.loser() == loser
+        assert outcome.is_decisive() == (winner is not None)
+
+
+def test_invalid_player_raises() -> None:
+    game = spooky_connect4.Game(width=7, height=6)
+    with pytest.raises(ValueError):
+        game.set_piece(0, 0, 3)
+    assert game.get_piece(0, 0) is None
+
+    for col in [0, 1, 0, 1, 0, 1, 0]:
+        assert game.push(col)
+    with pytest.raises(ValueError):
+        game.reward_from_perspective(0)
+
+    outcome = game.outcome()
+    assert outcome is not None
+    with pytest.raises(ValueError):
+        outcome.encode_winner_from_perspective(5)
+    with pytest.raises(ValueError):
+        outcome.relative_to(-2)
+
+
+def test_last_move() -> None:
+    game = spooky_connect4.Game(width=7, height=6)
+    assert game.last_move() is None
+
+    assert game.push(2)
+    assert game.push(2)
+    last = game.last_move()
+    assert last is not None
+    assert (last.col(), last.row()) == (2, 1)
+
+    assert game.unmake_move()
+    last = game.last_move()
+    assert last is not None
+    assert (last.col(), last.row()) == (2, 0)
+
+
+def test_winner() -> None:
+    game = spooky_connect4.Game(width=7, height=6)
+    assert game.winner() is None
+    for col in [0, 1, 0, 1, 0, 1, 0]:
+        assert game.push(col)
+    assert game.winner() == spooky_connect4.RED
+
+    drawn = spooky_connect4.Game(width=4, height=4)
+    for col in [1, 1, 2, 3, 0, 0, 3, 2, 1, 1, 3, 2, 3, 2, 0, 0]:
+        assert drawn.push(col)
+    assert drawn.is_over()
+    assert drawn.winner() is None
+
+
+def test_terminal_value_distinguishes_ongoing_from_draw() -> None:
+    game = spooky_connect4.Game(width=4, height=4)
+    assert game.terminal_value() is None
+    assert game.reward_absolute() == 0.0
+
+    for col in [1, 1, 2, 3, 0, 0, 3, 2, 1, 1, 3, 2, 3, 2, 0, 0]:
+        assert game.push(col)
+    assert game.terminal_value() == 0.0
+
+    won = spooky_connect4.Game(width=7, height=6)
+    for col in [0, 1, 0, 1, 0, 1, 0]:
+        assert won.push(col)
+    assert won.terminal_value() == 1.0
+
+
+def test_to_grid_matches_get_piece() -> None:
+    game = spooky_connect4.Game(width=9, height=9)
+    for col in [4, 4, 3, 8, 4, 0, 8, 8]:
+        assert game.apply_action(col)
+
+    grid = game.to_grid()
+    assert len(grid) == 9
+    for row in range(9):
+        assert len(grid[row]) == 9
+        for col in range(9):
+            assert grid[row][col] == game.get_piece(col, row)
+    assert grid[0][4] == spooky_connect4.RED
+    assert grid[2][8] == spooky_connect4.YELLOW
+
+
+def test_legal_action_indices_follow_mutations() -> None:
+    game = spooky_connect4.Game(width=7, height=6)
+    assert game.legal_action_indices() == list(range(7))
+    assert game.legal_action_indices() == list(range(7))
+
+    for _ in range(6):
+        assert game.push(2)
+    assert game.legal_action_indices() == [0, 1, 3, 4, 5, 6]
+    assert [m.col() for m in game.legal_moves()] == [0, 1, 3, 4, 5, 6]
+
+    game.pop()
+    assert game.legal_action_indices() == list(range(7))
+
+    game.set_piece(2, 5, spooky_connect4.RED)
+    assert game.legal_action_indices() == [0, 1, 3, 4, 5, 6]
+
+    game.reset()
+    assert game.legal_action_indices() == list(range(7))
+
+
+def test_stable_hash_same_position() -> None:
+    a = spooky_connect4.Game(width=7, height=6)
+    for col in [3, 4, 2]:
+        assert a.push(col)
+    b = spooky_connect4.Game(width=7, height=6)
+    for col in [2, 4, 3]:
+        assert b.push(col)
+
+    assert a.stable_hash() == b.stable_hash()
+    assert a.stable_hash() != spooky_connect4.Game(width=7, height=6).stable_hash()
+    # Pinned value: the hash must not change between processes or releases.
+    assert spooky_connect4.Game(width=7, height=6).stable_hash() == 0x540136EE551BD0AB
+
+
+def test_apply_action_checked_failure_modes() -> None:
+    game = spooky_connect4.Game(width=7, height=6)
+    assert game.apply_action_checked(3) == "ok"
+    assert game.apply_action_checked(7) == "out_of_range"
+    assert game.apply_action_checked(1000) == "out_of_range"
+
+    for _ in range(5):
+        assert game.apply_action_checked(3) == "ok"
+    assert game.apply_action_checked(3) == "column_full"
+    assert game.ply() == 6
+
+    for col in [0, 1, 0, 1, 0, 1, 0]:
+        assert game.apply_action_checked(col) == "ok"
+    assert game.is_over()
+    assert game.apply_action_checked(2) == "game_over"
+    assert game.apply_action_checked(7) == "game_over"
+    assert game.ply() == 13
+
+
+def test_outcome_after() -> None:
+    game = spooky_connect4.Game(width=7, height=6)
+    for col in [0, 1, 0, 1, 0, 1]:
+        assert game.push(col)
+
+    assert game.outcome_after(spooky_connect4.Move(0, 3)) == spooky_connect4.GameOutcome("RedWin")
+    assert game.outcome_after(spooky_connect4.Move(4, 0)) is None
+    with pytest.raises(ValueError):
+        game.outcome_after(spooky_connect4.Move(4, 2))
+    assert game.ply() == 6
+    assert not game.is_over()
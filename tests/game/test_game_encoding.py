@@ -24,12 +24,9 @@ def test_random_game_with_encoding() -> None:
     # Should have at least one encoding
     assert len(encodings) > 0
 
-    # All encodings should have the same structure (tuple of data, num_planes, height, width)
-    for data, num_planes, height, width in encodings:
-        assert num_planes == 17  # Total planes
-        assert height == 6
-        assert width == 7
-        assert len(data) == num_planes * height * width
+    # All encodings should have the same (num_planes, height, width) shape
+    for planes in encodings:
+        assert planes.shape == (17, 6, 7)
 
 
 def test_encode_decode_all_moves() -> None:
@@ -41,3 +41,39 @@ def test_move_in_set() -> None:
     move_set = {move1, move3}
     assert move2 in move_set
     assert move3 in move_set
+
+
+def test_move_str() -> None:
+    assert str(spooky_connect4.Move(3, 2)) == "col 3, row 2"
+
+
+def test_move_ordering() -> None:
+    moves = [
+        spooky_connect4.Move(3, 0),
+        spooky_connect4.Move(0, 2),
+        spooky_connect4.Move(3, 1),
+        spooky_connect4.Move(0, 0),
+        spooky_connect4.Move(6, 0),
+    ]
+    assert [(m.col(), m.row()) for m in sorted(moves)] == [(0, 0), (0, 2), (3, 0), (3, 1), (6, 0)]
+
+    a = spooky_connect4.Move(1, 5)
+    b = spooky_connect4.Move(2, 0)
+    assert a < b
+    assert a <= b
+    assert b > a
+    assert b >= a
+    assert a <= spooky_connect4.Move(1, 5)
+    assert not a < spooky_connect4.Move(1, 5)
+
+
+def test_move_is_valid_for() -> None:
+    game = spooky_connect4.Game(width=7, height=6)
+
+    floating = spooky_connect4.Move(3, 2)
+    assert floating.is_valid_for(game)
+    assert not game.is_legal_move(floating)
+
+    assert not spooky_connect4.Move(7, 0).is_valid_for(game)
+    assert not spooky_connect4.Move(0, 6).is_valid_for(game)
+    assert spooky_connect4.Move(7, 0).is_valid_for(spooky_connect4.Game(width=9, height=9))
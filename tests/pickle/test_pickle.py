@@ -0,0 +1,69 @@
+import pickle
+
+import spooky_connect4
+
+
+def test_pickle_game_preserves_hash_and_legal_moves() -> None:
+    game = spooky_connect4.Game(width=7, height=6)
+    for action in [3, 3, 4, 2, 0, 0]:
+        game.apply_action(action)
+
+    restored = pickle.loads(pickle.dumps(game))
+
+    assert hash(restored) == hash(game)
+    assert restored.legal_moves() == game.legal_moves()
+    assert restored.turn() == game.turn()
+    assert restored.encode_game_planes().tolist() == game.encode_game_planes().tolist()
+
+    # The restored game keeps its history and can be played on
+    assert restored.unmake_move()
+    assert restored.get_piece(0, 1) is None
+
+
+def test_pickle_game_large_board() -> None:
+    game = spooky_connect4.Game(width=19, height=17)
+    for action in [0, 18, 9, 9]:
+        game.apply_action(action)
+
+    restored = pickle.loads(pickle.dumps(game))
+
+    assert (restored.width(), restored.height()) == (19, 17)
+    assert hash(restored) == hash(game)
+    assert restored.legal_moves() == game.legal_moves()
+
+
+def test_pickle_finished_game() -> None:
+    game = spooky_connect4.Game(width=7, height=6)
+    for action in [0, 1, 0, 1, 0, 1, 0]:
+        game.apply_action(action)
+
+    restored = pickle.loads(pickle.dumps(game))
+
+    assert restored.is_over()
+    assert restored.outcome() == game.outcome()
+    assert restored.legal_moves() == []
+
+
+def test_pickle_board() -> None:
+    board = spooky_connect4.Board(width=9, height=9)
+    board.set_piece(0, 0, spooky_connect4.RED)
+    board.set_piece(8, 0, spooky_connect4.YELLOW)
+
+    restored = pickle.loads(pickle.dumps(board))
+
+    assert (restored.width(), restored.height()) == (9, 9)
+    assert restored.get_piece(0, 0) == spooky_connect4.RED
+    assert restored.get_piece(8, 0) == spooky_connect4.YELLOW
+    assert restored.total_count() == 2
+
+
+def test_pickle_move_and_outcome() -> None:
+    move = spooky_connect4.Move(3, 2)
+    assert pickle.loads(pickle.dumps(move)) == move
+
+    game = spooky_connect4.Game(width=7, height=6)
+    for action in [0, 1, 0, 1, 0, 1, 0]:
+        game.apply_action(action)
+    outcome = game.outcome()
+    assert outcome is not None
+    assert pickle.loads(pickle.dumps(outcome)) == outcome